@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    UInt(u64),
+    Str(String),
+    /// A multi-line rendering, e.g. letters drawn with `#` and `.`
+    Grid(String),
+}
+
+impl Answer {
+    /// Compares against an expected answer, such as one read from a file. Surrounding whitespace is ignored, as is the
+    /// distinction between signed and unsigned integers.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = expected.trim();
+        match self {
+            Answer::Int(i) => expected.parse::<i128>().is_ok_and(|e| e == *i),
+            Answer::UInt(u) => expected.parse::<u64>().is_ok_and(|e| e == *u),
+            Answer::Str(s) => s.trim() == expected,
+            Answer::Grid(g) => g
+                .trim()
+                .lines()
+                .map(str::trim_end)
+                .eq(expected.lines().map(str::trim_end)),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(i) => write!(f, "{i}"),
+            Answer::UInt(u) => write!(f, "{u}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::Grid(g) => write!(f, "\n{g}"),
+        }
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self { Answer::Int(value as i128) }
+        })*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self { Answer::UInt(value as u64) }
+        })*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match u64::try_from(value) {
+            Ok(v) => Answer::UInt(v),
+            Err(_) => Answer::Str(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-42i64).to_string(), "-42");
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::Grid("#.\n.#".to_string()).to_string(), "\n#.\n.#");
+    }
+
+    #[test]
+    fn test_answer_matches() {
+        assert!(Answer::from(-42i32).matches("-42"));
+        assert!(Answer::from(42u32).matches(" 42\n"));
+        assert!(!Answer::from(42u32).matches("43"));
+        assert!(!Answer::from(42u32).matches("forty-two"));
+        assert!(Answer::from("ABCD").matches("ABCD\n"));
+        assert!(Answer::Grid("\n#. \n.#\n".to_string()).matches("#.\n.#"));
+        assert!(Answer::from(7u8).matches("7"));
+        assert_ne!(Answer::from(7u8), Answer::from("7"));
+    }
+}
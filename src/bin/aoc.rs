@@ -0,0 +1,123 @@
+//! Account and leaderboard commands for adventofcode.com. `login` and `logout` need the `keyring` feature and
+//! `leaderboard` needs the `client` feature.
+
+use anyhow::Result;
+#[cfg(feature = "client")]
+use aoc_util::config::Config;
+#[cfg(feature = "client")]
+use aoc_util::leaderboard;
+#[cfg(any(feature = "keyring", feature = "client"))]
+use aoc_util::{config, session};
+#[cfg(any(feature = "keyring", feature = "client"))]
+use clap::{Parser, Subcommand};
+#[cfg(feature = "keyring")]
+use std::io::{BufRead, IsTerminal, Write};
+#[cfg(feature = "keyring")]
+use std::process::{Command, Stdio};
+
+#[cfg(any(feature = "keyring", feature = "client"))]
+#[derive(Debug, Parser)]
+#[command(name = "aoc", version, author)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[cfg(any(feature = "keyring", feature = "client"))]
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// store the adventofcode.com session cookie in the OS keyring, taken from AOC_SESSION if set, otherwise read
+    /// from stdin (without echo at a terminal)
+    #[cfg(feature = "keyring")]
+    Login,
+
+    /// remove the stored session cookie from the OS keyring
+    #[cfg(feature = "keyring")]
+    Logout,
+
+    /// print a private leaderboard ranked by local score, cached for 15 minutes
+    #[cfg(feature = "client")]
+    Leaderboard {
+        /// leaderboard ID, from its URL
+        id: u64,
+
+        /// puzzle year
+        #[arg(long, env = config::YEAR_ENV)]
+        year: Option<u16>,
+    },
+}
+
+#[cfg(any(feature = "keyring", feature = "client"))]
+fn main() -> Result<()> {
+    match Args::parse().command {
+        #[cfg(feature = "keyring")]
+        Commands::Login => {
+            let session = match std::env::var(config::SESSION_ENV) {
+                Ok(session) => session,
+                Err(_) => read_session()?,
+            };
+
+            if session.trim().is_empty() {
+                anyhow::bail!("No session cookie provided");
+            }
+
+            session::store(&session)?;
+            println!("Session cookie stored");
+        }
+        #[cfg(feature = "keyring")]
+        Commands::Logout => {
+            if session::clear()? {
+                println!("Session cookie removed");
+            } else {
+                println!("No session cookie stored");
+            }
+        }
+        #[cfg(feature = "client")]
+        Commands::Leaderboard { id, year } => {
+            let mut config = Config::load()?.merge(Config::from_env()?);
+            config.year = year.or(config.year);
+            config.session = session::resolve(&config);
+            leaderboard::show(&config, id)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(feature = "keyring", feature = "client")))]
+fn main() -> Result<()> {
+    anyhow::bail!(
+        "aoc was built without the `keyring` and `client` features, so it has no commands"
+    )
+}
+
+/// Reads the cookie from stdin, so it never appears in shell history or `ps` output. At a terminal it prompts and turns
+/// off echo while the cookie is typed.
+#[cfg(feature = "keyring")]
+fn read_session() -> Result<String> {
+    let stdin = std::io::stdin();
+    let terminal = stdin.is_terminal();
+    if terminal {
+        print!("Session cookie: ");
+        std::io::stdout().flush()?;
+        set_echo(false);
+    }
+
+    let mut line = String::new();
+    let read = stdin.lock().read_line(&mut line);
+    if terminal {
+        set_echo(true);
+        println!();
+    }
+    read?;
+    Ok(line)
+}
+
+/// Turns terminal echo on or off with `stty`, where available; without it, the cookie is merely echoed as it's typed.
+#[cfg(feature = "keyring")]
+fn set_echo(on: bool) {
+    let _ = Command::new("stty")
+        .arg(if on { "echo" } else { "-echo" })
+        .stdin(Stdio::inherit())
+        .status();
+}
@@ -0,0 +1,25 @@
+use anyhow::{Result, anyhow};
+use log::debug;
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(
+    "github.com/jluszcz/AdventOfCode-rs ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Fetches `path` from adventofcode.com, authenticated with the given session cookie.
+pub fn get(path: &str, session: &str) -> Result<String> {
+    let url = format!("{BASE_URL}{path}");
+    debug!("GET {url}");
+
+    ureq::get(&url)
+        .header("Cookie", format!("session={session}"))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| anyhow!("GET {url} failed: {e}"))?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| anyhow!("Failed to read response from {url}: {e}"))
+}
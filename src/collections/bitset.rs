@@ -0,0 +1,316 @@
+use super::Interner;
+use anyhow::{Result, bail};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, Sub};
+
+macro_rules! bitset {
+    ($name:ident, $subsets:ident, $bits:ty) => {
+        /// A set of small indices packed into a single integer, so it is `Copy` and cheap to hash — handy as part of a
+        /// search state.
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name($bits);
+
+        impl $name {
+            pub const CAPACITY: usize = <$bits>::BITS as usize;
+
+            pub fn new() -> Self {
+                Self(0)
+            }
+
+            pub fn from_bits(bits: $bits) -> Self {
+                Self(bits)
+            }
+
+            pub fn bits(self) -> $bits {
+                self.0
+            }
+
+            /// The set `{0, 1, ..., n - 1}`.
+            pub fn full(n: usize) -> Self {
+                assert!(
+                    n <= Self::CAPACITY,
+                    "{n} exceeds capacity {}",
+                    Self::CAPACITY
+                );
+                Self(
+                    <$bits>::MAX
+                        .checked_shr(Self::CAPACITY as u32 - n as u32)
+                        .unwrap_or(0),
+                )
+            }
+
+            fn bit(i: usize) -> $bits {
+                assert!(
+                    i < Self::CAPACITY,
+                    "index {i} out of range for {}",
+                    stringify!($name)
+                );
+                1 << i
+            }
+
+            /// Adds `i`, returning whether it was newly inserted.
+            pub fn insert(&mut self, i: usize) -> bool {
+                let inserted = !self.contains(i);
+                self.0 |= Self::bit(i);
+                inserted
+            }
+
+            /// Removes `i`, returning whether it was present.
+            pub fn remove(&mut self, i: usize) -> bool {
+                let removed = self.contains(i);
+                self.0 &= !Self::bit(i);
+                removed
+            }
+
+            pub fn contains(self, i: usize) -> bool {
+                self.0 & Self::bit(i) != 0
+            }
+
+            /// A copy with `i` added.
+            pub fn with(mut self, i: usize) -> Self {
+                self.insert(i);
+                self
+            }
+
+            /// A copy with `i` removed.
+            pub fn without(mut self, i: usize) -> Self {
+                self.remove(i);
+                self
+            }
+
+            pub fn len(self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            pub fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            pub fn is_subset(self, other: Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            /// The smallest index in the set.
+            pub fn first(self) -> Option<usize> {
+                (!self.is_empty()).then(|| self.0.trailing_zeros() as usize)
+            }
+
+            /// Set indices in increasing order.
+            pub fn iter(self) -> impl Iterator<Item = usize> {
+                let mut bits = self.0;
+                std::iter::from_fn(move || {
+                    let i = (bits != 0).then(|| bits.trailing_zeros() as usize)?;
+                    bits &= bits - 1;
+                    Some(i)
+                })
+            }
+
+            /// Every subset, from the set itself down to the empty set.
+            pub fn subsets(self) -> $subsets {
+                $subsets {
+                    mask: self.0,
+                    next: Some(self.0),
+                }
+            }
+        }
+
+        /// Iterator over the subsets of a set, returned by its `subsets` method.
+        #[derive(Debug, Clone)]
+        pub struct $subsets {
+            mask: $bits,
+            next: Option<$bits>,
+        }
+
+        impl Iterator for $subsets {
+            type Item = $name;
+
+            fn next(&mut self) -> Option<$name> {
+                let current = self.next?;
+                self.next = (current != 0).then(|| (current - 1) & self.mask);
+                Some($name(current))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 & !rhs.0)
+            }
+        }
+
+        impl FromIterator<usize> for $name {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut set = Self::new();
+                iter.into_iter().for_each(|i| {
+                    set.insert(i);
+                });
+                set
+            }
+        }
+
+        impl Extend<usize> for $name {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                iter.into_iter().for_each(|i| {
+                    self.insert(i);
+                });
+            }
+        }
+    };
+}
+
+bitset!(BitSet64, Subsets64, u64);
+bitset!(BitSet128, Subsets128, u128);
+
+/// Assigns labels (valve names, keys) to bit positions in a [`BitSet64`], in order of first appearance.
+#[derive(Debug, Clone)]
+pub struct BitLabels<T>(Interner<T>);
+
+impl<T> Default for BitLabels<T> {
+    fn default() -> Self {
+        Self(Interner::default())
+    }
+}
+
+impl<T: Clone + Eq + Hash> BitLabels<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The bit position of `label`, assigning the next free one if it is new.
+    pub fn intern(&mut self, label: &T) -> Result<usize> {
+        if let Some(i) = self.0.id(label) {
+            return Ok(i);
+        }
+        if self.len() == BitSet64::CAPACITY {
+            bail!("Too many labels for a 64-bit set");
+        }
+        Ok(self.0.intern(label))
+    }
+
+    pub fn position(&self, label: &T) -> Option<usize> {
+        self.0.id(label)
+    }
+
+    pub fn label(&self, i: usize) -> Option<&T> {
+        self.0.label(i)
+    }
+
+    /// The set of `labels`, interning any that are new.
+    pub fn set<'a>(&mut self, labels: impl IntoIterator<Item = &'a T>) -> Result<BitSet64>
+    where
+        T: 'a,
+    {
+        labels.into_iter().map(|l| self.intern(l)).collect()
+    }
+
+    /// The labels in `set`.
+    pub fn labels(&self, set: BitSet64) -> impl Iterator<Item = &T> {
+        set.iter().filter_map(|i| self.label(i))
+    }
+
+    /// The set of every label assigned so far.
+    pub fn all(&self) -> BitSet64 {
+        BitSet64::full(self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        let mut set = BitSet64::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(63));
+        set.extend([0, 5]);
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(63));
+        assert!(!set.contains(4));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 3, 5, 63]);
+        assert_eq!(set.first(), Some(0));
+        assert_eq!(format!("{set:?}"), "{0, 3, 5, 63}");
+
+        assert!(set.remove(0));
+        assert!(!set.remove(0));
+        assert_eq!(
+            set.without(3).with(1).iter().collect::<Vec<_>>(),
+            [1, 5, 63]
+        );
+
+        let other: BitSet64 = [3, 4].into_iter().collect();
+        assert_eq!((set | other).len(), 4);
+        assert_eq!((set & other).iter().collect::<Vec<_>>(), [3]);
+        assert_eq!((set - other).iter().collect::<Vec<_>>(), [5, 63]);
+        assert!((set & other).is_subset(set));
+        assert!(!other.is_subset(set));
+
+        assert_eq!(BitSet64::full(0), BitSet64::new());
+        assert_eq!(BitSet64::full(64).len(), 64);
+        assert_eq!(BitSet128::full(100).len(), 100);
+        assert_eq!(BitSet128::new().with(127).bits(), 1 << 127);
+    }
+
+    #[test]
+    fn test_bitset_subsets() {
+        let set = BitSet64::from_bits(0b10110);
+        let subsets: Vec<_> = set.subsets().map(BitSet64::bits).collect();
+        assert_eq!(
+            subsets,
+            [0b10110, 0b10100, 0b10010, 0b10000, 0b110, 0b100, 0b10, 0]
+        );
+        assert_eq!(BitSet64::new().subsets().count(), 1);
+        assert_eq!(BitSet128::full(10).subsets().count(), 1024);
+    }
+
+    #[test]
+    fn test_bit_labels() {
+        let mut valves = BitLabels::new();
+        let open = valves.set(&["BB", "DD", "BB"]).unwrap();
+        assert_eq!(open.len(), 2);
+        assert_eq!(valves.intern(&"JJ").unwrap(), 2);
+        assert_eq!(valves.position(&"DD"), Some(1));
+        assert_eq!(valves.position(&"AA"), None);
+        assert_eq!(valves.label(2), Some(&"JJ"));
+        assert_eq!(valves.labels(open).collect::<Vec<_>>(), [&"BB", &"DD"]);
+        assert_eq!(valves.all().len(), 3);
+
+        let mut many = BitLabels::new();
+        assert!((0..64).all(|i| many.intern(&i).is_ok()));
+        assert!(many.intern(&64).is_err());
+        assert!(many.intern(&10).is_ok());
+    }
+}
@@ -0,0 +1,336 @@
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    value: Option<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// A circular doubly linked list with a cursor, backed by an arena so inserts, removals and moves are O(1).
+///
+/// Elements are addressed by stable ids: collecting from an iterator assigns ids `0..n` in order, and every insert
+/// returns the new element's id. Ids of removed elements may be reused.
+#[derive(Debug, Clone)]
+pub struct Circle<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    cursor: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for Circle<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            cursor: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> Circle<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Id of the element under the cursor.
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.cursor.and_then(|id| self.get(id))
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.cursor.and_then(|id| self.get_mut(id))
+    }
+
+    pub fn get(&self, id: usize) -> Option<&T> {
+        self.slots.get(id)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut T> {
+        self.slots.get_mut(id)?.value.as_mut()
+    }
+
+    fn check(&self, id: usize) {
+        assert!(self.get(id).is_some(), "no element with id {id}");
+    }
+
+    /// Id of the element clockwise of `id`.
+    pub fn next(&self, id: usize) -> usize {
+        self.check(id);
+        self.slots[id].next
+    }
+
+    /// Id of the element counter-clockwise of `id`.
+    pub fn prev(&self, id: usize) -> usize {
+        self.check(id);
+        self.slots[id].prev
+    }
+
+    /// Moves the cursor to the element with the given id.
+    pub fn seek(&mut self, id: usize) {
+        self.check(id);
+        self.cursor = Some(id);
+    }
+
+    /// Moves the cursor `steps` elements clockwise, or counter-clockwise if negative.
+    pub fn rotate(&mut self, steps: isize) {
+        let Some(mut id) = self.cursor else {
+            return;
+        };
+        let steps = steps % self.len as isize;
+        for _ in 0..steps.unsigned_abs() {
+            id = if steps > 0 {
+                self.slots[id].next
+            } else {
+                self.slots[id].prev
+            };
+        }
+        self.cursor = Some(id);
+    }
+
+    fn allocate(&mut self, value: T) -> usize {
+        let slot = Slot {
+            value: Some(value),
+            prev: 0,
+            next: 0,
+        };
+        self.len += 1;
+        match self.free.pop() {
+            Some(id) => {
+                self.slots[id] = slot;
+                id
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    fn link_after(&mut self, id: usize, after: usize) {
+        let next = self.slots[after].next;
+        self.slots[id].prev = after;
+        self.slots[id].next = next;
+        self.slots[after].next = id;
+        self.slots[next].prev = id;
+    }
+
+    fn unlink(&mut self, id: usize) {
+        let Slot { prev, next, .. } = self.slots[id];
+        self.slots[prev].next = next;
+        self.slots[next].prev = prev;
+    }
+
+    /// Inserts `value` clockwise of the cursor and moves the cursor onto it, returning its id.
+    pub fn insert(&mut self, value: T) -> usize {
+        let id = match self.cursor {
+            Some(cursor) => self.insert_after(cursor, value),
+            None => {
+                let id = self.allocate(value);
+                self.slots[id].prev = id;
+                self.slots[id].next = id;
+                id
+            }
+        };
+        self.cursor = Some(id);
+        id
+    }
+
+    /// Inserts `value` clockwise of `after` without moving the cursor, returning its id.
+    pub fn insert_after(&mut self, after: usize, value: T) -> usize {
+        self.check(after);
+        let id = self.allocate(value);
+        self.link_after(id, after);
+        id
+    }
+
+    /// Removes the element under the cursor, moving the cursor clockwise.
+    pub fn remove(&mut self) -> Option<T> {
+        let id = self.cursor?;
+        self.remove_id(id)
+    }
+
+    /// Removes the element with the given id. If it was under the cursor, the cursor moves clockwise.
+    pub fn remove_id(&mut self, id: usize) -> Option<T> {
+        let value = self.slots.get_mut(id)?.value.take()?;
+        let next = self.slots[id].next;
+        self.unlink(id);
+        self.free.push(id);
+        self.len -= 1;
+
+        if self.cursor == Some(id) {
+            self.cursor = (self.len > 0).then_some(next);
+        }
+        Some(value)
+    }
+
+    /// Moves the element `id` so it sits clockwise of `after`, keeping its id.
+    pub fn move_after(&mut self, id: usize, after: usize) {
+        self.check(id);
+        self.check(after);
+        if id == after || self.slots[after].next == id {
+            return;
+        }
+        if self.cursor == Some(id) {
+            self.cursor = Some(self.slots[id].next);
+        }
+        self.unlink(id);
+        self.link_after(id, after);
+    }
+
+    /// Iterates clockwise once around the circle, starting at the cursor.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cursor
+            .into_iter()
+            .flat_map(move |start| self.iter_from(start))
+    }
+
+    /// Iterates clockwise once around the circle, starting at `id`.
+    pub fn iter_from(&self, id: usize) -> impl Iterator<Item = &T> {
+        self.check(id);
+        let mut next = Some(id);
+        std::iter::from_fn(move || {
+            let current = next?;
+            let following = self.slots[current].next;
+            next = (following != id).then_some(following);
+            self.slots[current].value.as_ref()
+        })
+    }
+}
+
+/// Collects values in clockwise order with ids `0..n`, leaving the cursor on the first.
+impl<T> FromIterator<T> for Circle<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut circle = Self::new();
+        for value in iter {
+            circle.insert(value);
+        }
+        circle.rotate(1);
+        circle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marble_high_score(players: usize, last_marble: u64) -> u64 {
+        let mut scores = vec![0; players];
+        let mut circle = Circle::with_capacity(last_marble as usize + 1);
+        circle.insert(0);
+
+        for marble in 1..=last_marble {
+            if marble % 23 == 0 {
+                circle.rotate(-7);
+                scores[marble as usize % players] += marble + circle.remove().unwrap();
+            } else {
+                circle.rotate(1);
+                circle.insert(marble);
+            }
+        }
+        scores.into_iter().max().unwrap()
+    }
+
+    fn crab_cups(labels: &str, moves: usize) -> String {
+        let labels: Vec<usize> = labels.bytes().map(|b| (b - b'0') as usize).collect();
+        let mut ids = vec![0; labels.len() + 1];
+        for (id, &label) in labels.iter().enumerate() {
+            ids[label] = id;
+        }
+        let mut cups: Circle<usize> = labels.into_iter().collect();
+
+        for _ in 0..moves {
+            let current = cups.cursor().unwrap();
+            let first = cups.next(current);
+            let second = cups.next(first);
+            let third = cups.next(second);
+            let picked = [first, second, third].map(|id| *cups.get(id).unwrap());
+
+            let mut destination = *cups.current().unwrap();
+            loop {
+                destination = if destination == 1 {
+                    ids.len() - 1
+                } else {
+                    destination - 1
+                };
+                if !picked.contains(&destination) {
+                    break;
+                }
+            }
+
+            let destination = ids[destination];
+            cups.move_after(first, destination);
+            cups.move_after(second, first);
+            cups.move_after(third, second);
+            cups.rotate(1);
+        }
+
+        cups.iter_from(ids[1])
+            .skip(1)
+            .map(|label| label.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_circle_marbles() {
+        assert_eq!(marble_high_score(9, 25), 32);
+        assert_eq!(marble_high_score(10, 1618), 8317);
+        assert_eq!(marble_high_score(13, 7999), 146373);
+    }
+
+    #[test]
+    fn test_circle_crab_cups() {
+        assert_eq!(crab_cups("389125467", 10), "92658374");
+        assert_eq!(crab_cups("389125467", 100), "67384529");
+    }
+
+    #[test]
+    fn test_circle_edits() {
+        let mut circle: Circle<char> = "abcd".chars().collect();
+        assert_eq!(circle.current(), Some(&'a'));
+        assert_eq!(circle.len(), 4);
+
+        circle.rotate(-1);
+        assert_eq!(circle.current(), Some(&'d'));
+        circle.rotate(6);
+        assert_eq!(circle.current(), Some(&'b'));
+
+        assert_eq!(circle.remove(), Some('b'));
+        assert_eq!(circle.iter().collect::<String>(), "cda");
+        assert_eq!(circle.remove_id(1), None);
+
+        let e = circle.insert_after(0, 'e');
+        assert_eq!(e, 1);
+        assert_eq!(circle.iter().collect::<String>(), "cdae");
+        circle.move_after(2, 3);
+        assert_eq!(circle.current(), Some(&'d'));
+        assert_eq!(circle.iter_from(0).collect::<String>(), "aedc");
+
+        while circle.remove().is_some() {}
+        assert!(circle.is_empty());
+        assert_eq!(circle.cursor(), None);
+        assert_eq!(circle.iter().count(), 0);
+        circle.rotate(3);
+        circle.insert('z');
+        assert_eq!(circle.iter().collect::<String>(), "z");
+    }
+}
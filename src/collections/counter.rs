@@ -0,0 +1,243 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+/// A multiset: counts of distinct items. Items with a count of zero are never stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash>(HashMap<T, u64>);
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, n: u64) {
+        if n > 0 {
+            *self.0.entry(item).or_default() += n;
+        }
+    }
+
+    /// Removes up to `n` of `item`, returning how many were removed.
+    pub fn remove_n(&mut self, item: &T, n: u64) -> u64 {
+        let Some(count) = self.0.get_mut(item) else {
+            return 0;
+        };
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.0.remove(item);
+        }
+        removed
+    }
+
+    pub fn get(&self, item: &T) -> u64 {
+        self.0.get(item).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> u64 {
+        self.0.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.0.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+/// Ties between equal counts go to the smallest item, so results don't depend on hash order.
+impl<T: Eq + Hash + Ord> Counter<T> {
+    /// All items with their counts, most common first, then in item order.
+    pub fn most_common(&self) -> Vec<(&T, u64)> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by_key(|&(item, count)| (Reverse(count), item));
+        items
+    }
+
+    /// The most common item, or the smallest of those tied for it.
+    pub fn max(&self) -> Option<(&T, u64)> {
+        self.iter()
+            .min_by_key(|&(item, count)| (Reverse(count), item))
+    }
+
+    /// The least common item, or the smallest of those tied for it.
+    pub fn min(&self) -> Option<(&T, u64)> {
+        self.iter().min_by_key(|&(item, count)| (count, item))
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, u64);
+    type IntoIter = hash_map::IntoIter<T, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (item, count) in rhs {
+            self.add_n(item, count);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+/// Subtraction saturates at zero, dropping items whose counts run out.
+impl<T: Eq + Hash> SubAssign for Counter<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (item, count) in rhs {
+            self.remove_n(&item, count);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+/// Scales every count.
+impl<T: Eq + Hash> Mul<u64> for Counter<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        if rhs == 0 {
+            return Self::new();
+        }
+        Self(
+            self.0
+                .into_iter()
+                .map(|(item, count)| (item, count * rhs))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_basics() {
+        let mut counter = "NNCB".chars().collect::<Counter<_>>();
+        assert_eq!(counter.get(&'N'), 2);
+        assert_eq!(counter.get(&'Z'), 0);
+        assert_eq!(counter.len(), 3);
+        assert_eq!(counter.total(), 4);
+
+        counter.add_n('B', 5);
+        counter.add_n('Z', 0);
+        assert_eq!(counter.most_common()[0], (&'B', 6));
+        assert_eq!(counter.max(), Some((&'B', 6)));
+        assert_eq!(counter.min(), Some((&'C', 1)));
+        assert_eq!(counter.len(), 3);
+
+        assert_eq!(counter.remove_n(&'C', 10), 1);
+        assert_eq!(counter.remove_n(&'C', 1), 0);
+        assert_eq!(counter.len(), 2);
+        assert!(!counter.is_empty());
+    }
+
+    #[test]
+    fn test_counter_ties() {
+        let counter = "aaaaabbbzyxyzz".chars().collect::<Counter<_>>();
+        assert_eq!(
+            counter.most_common(),
+            [(&'a', 5), (&'b', 3), (&'z', 3), (&'y', 2), (&'x', 1)]
+        );
+        assert_eq!(counter.max(), Some((&'a', 5)));
+
+        let counter = "dcbabcd".chars().collect::<Counter<_>>();
+        assert_eq!(counter.max(), Some((&'b', 2)));
+        assert_eq!(counter.min(), Some((&'a', 1)));
+        let counter = "ddccbb".chars().collect::<Counter<_>>();
+        assert_eq!(counter.min(), Some((&'b', 2)));
+        assert_eq!(Counter::<char>::new().max(), None);
+    }
+
+    #[test]
+    fn test_counter_arithmetic() {
+        let a = [1, 1, 2, 3].into_iter().collect::<Counter<_>>();
+        let b = [1, 3, 3, 4].into_iter().collect::<Counter<_>>();
+
+        let sum = a.clone() + b.clone();
+        assert_eq!(sum.get(&1), 3);
+        assert_eq!(sum.get(&3), 3);
+        assert_eq!(sum.total(), 8);
+
+        let diff = a.clone() - b;
+        assert_eq!(diff, [1, 2].into_iter().collect());
+
+        let scaled = a * 3;
+        assert_eq!(scaled.get(&1), 6);
+        assert_eq!(scaled.total(), 12);
+    }
+
+    #[test]
+    fn test_counter_lanternfish() {
+        let mut fish = [3u8, 4, 3, 1, 2].into_iter().collect::<Counter<_>>();
+        for _ in 0..256 {
+            let mut next = Counter::new();
+            for (timer, count) in fish {
+                if timer == 0 {
+                    next.add_n(6, count);
+                    next.add_n(8, count);
+                } else {
+                    next.add_n(timer - 1, count);
+                }
+            }
+            fish = next;
+        }
+        assert_eq!(fish.total(), 26_984_457_539);
+    }
+}
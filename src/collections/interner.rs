@@ -0,0 +1,107 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Index;
+
+/// Maps labels (valve names, wire names) to dense ids `0, 1, 2, ...` in order of first appearance, so per-label data
+/// can live in a `Vec` indexed by id instead of a `HashMap` keyed by the label.
+#[derive(Debug, Clone)]
+pub struct Interner<T = String> {
+    labels: Vec<T>,
+    ids: HashMap<T, usize>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// The id of `label`, assigning the next one if it is new.
+    pub fn intern<Q>(&mut self, label: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: ToOwned<Owned = T> + Eq + Hash + ?Sized,
+    {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.labels.push(label.to_owned());
+        self.ids.insert(label.to_owned(), id);
+        id
+    }
+
+    /// The id of `label`, if it has been interned.
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> Option<&T> {
+        self.labels.get(id)
+    }
+
+    /// Every `(id, label)` pair, by id.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.labels.iter().enumerate()
+    }
+}
+
+impl<T> Index<usize> for Interner<T> {
+    type Output = T;
+
+    fn index(&self, id: usize) -> &Self::Output {
+        &self.labels[id]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let mut valves = Interner::new();
+        assert_eq!(valves.intern("AA"), 0);
+        assert_eq!(valves.intern("DD"), 1);
+        assert_eq!(valves.intern("AA"), 0);
+        assert_eq!(valves.len(), 2);
+
+        assert_eq!(valves.id("DD"), Some(1));
+        assert_eq!(valves.id("ZZ"), None);
+        assert_eq!(valves[1], "DD");
+        assert_eq!(valves.label(2), None);
+        assert_eq!(
+            valves
+                .iter()
+                .map(|(i, l)| (i, l.as_str()))
+                .collect::<Vec<_>>(),
+            [(0, "AA"), (1, "DD")]
+        );
+
+        let mut points = Interner::new();
+        assert_eq!(points.intern(&(3, 4)), 0);
+        assert_eq!(points.intern(&(1, 1)), 1);
+        assert_eq!(points[0], (3, 4));
+    }
+}
@@ -0,0 +1,17 @@
+mod bitset;
+mod circle;
+mod counter;
+mod interner;
+mod priority_queue;
+mod range_map;
+mod sorted_vec;
+mod trie;
+
+pub use bitset::{BitLabels, BitSet64, BitSet128, Subsets64, Subsets128};
+pub use circle::Circle;
+pub use counter::Counter;
+pub use interner::Interner;
+pub use priority_queue::{IndexedPriorityQueue, MinHeap};
+pub use range_map::RangeMap;
+pub use sorted_vec::SortedVec;
+pub use trie::Trie;
@@ -0,0 +1,258 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// A min-heap, so callers don't need to wrap items in [`Reverse`].
+#[derive(Debug, Clone)]
+pub struct MinHeap<T: Ord>(BinaryHeap<Reverse<T>>);
+
+impl<T: Ord> Default for MinHeap<T> {
+    fn default() -> Self {
+        Self(BinaryHeap::new())
+    }
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.0.push(Reverse(item));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop().map(|Reverse(item)| item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.0.peek().map(|Reverse(item)| item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Reverse).collect())
+    }
+}
+
+impl<T: Ord> Extend<T> for MinHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(Reverse));
+    }
+}
+
+/// A binary min-heap of keys ordered by priority, holding each key at most once so its priority can be changed in
+/// place. Use [`Reverse`] priorities for max-first ordering, or any `Ord` type for a custom one.
+#[derive(Debug, Clone)]
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K, P> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, P: Ord> IndexedPriorityQueue<K, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(k, p)| (k, p))
+    }
+
+    /// Inserts `key`, or changes its priority if it's already queued.
+    pub fn push(&mut self, key: K, priority: P) {
+        match self.positions.get(&key) {
+            Some(&i) => {
+                let increased = priority > self.heap[i].1;
+                self.heap[i].1 = priority;
+                if increased {
+                    self.sift_down(i);
+                } else {
+                    self.sift_up(i);
+                }
+            }
+            None => {
+                let i = self.heap.len();
+                self.positions.insert(key.clone(), i);
+                self.heap.push((key, priority));
+                self.sift_up(i);
+            }
+        }
+    }
+
+    /// Inserts `key`, or lowers its priority if `priority` is better than the queued one. Returns whether the queue
+    /// changed, which is exactly when a Dijkstra-style relaxation succeeds.
+    pub fn push_decrease(&mut self, key: K, priority: P) -> bool {
+        if let Some(&i) = self.positions.get(&key) {
+            if priority >= self.heap[i].1 {
+                return false;
+            }
+            self.heap[i].1 = priority;
+            self.sift_up(i);
+        } else {
+            self.push(key, priority);
+        }
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (key, priority) = self.swap_remove(0);
+        Some((key, priority))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        Some(self.swap_remove(i).1)
+    }
+
+    fn swap_remove(&mut self, i: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let removed = self.heap.pop().expect("heap is non-empty");
+        self.positions.remove(&removed.0);
+        if i < self.heap.len() {
+            self.sift_down(i);
+            self.sift_up(i);
+        }
+        removed
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions.insert(self.heap[a].0.clone(), a);
+        self.positions.insert(self.heap[b].0.clone(), b);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, P: Ord> FromIterator<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        for (key, priority) in iter {
+            queue.push(key, priority);
+        }
+        queue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap = [5, 1, 4].into_iter().collect::<MinHeap<_>>();
+        heap.push(3);
+        heap.extend([2, 0]);
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek(), Some(&0));
+
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+        assert_eq!(popped, vec![0, 1, 2, 3, 4, 5]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_indexed_priority_queue() {
+        let mut queue = [("a", 5), ("b", 3), ("c", 8), ("d", 1)]
+            .into_iter()
+            .collect::<IndexedPriorityQueue<_, _>>();
+
+        assert_eq!(queue.peek(), Some((&"d", &1)));
+        assert!(queue.push_decrease("c", 0));
+        assert!(!queue.push_decrease("a", 6));
+        assert!(queue.push_decrease("e", 4));
+        queue.push("d", 10);
+
+        assert_eq!(queue.priority(&"a"), Some(&5));
+        assert_eq!(queue.remove(&"b"), Some(3));
+        assert_eq!(queue.remove(&"b"), None);
+        assert!(!queue.contains(&"b"));
+        assert_eq!(queue.len(), 4);
+
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop() {
+            popped.push(entry);
+        }
+        assert_eq!(popped, vec![("c", 0), ("e", 4), ("a", 5), ("d", 10)]);
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_max_first() {
+        let mut queue = IndexedPriorityQueue::new();
+        for (i, p) in [4, 9, 2, 7, 7, 1].into_iter().enumerate() {
+            queue.push(i, Reverse(p));
+        }
+        queue.push(5, Reverse(100));
+
+        let order = std::iter::from_fn(|| queue.pop().map(|(k, _)| k)).collect::<Vec<_>>();
+        assert_eq!(order[..2], [5, 1]);
+        assert_eq!(order.len(), 6);
+        assert_eq!(order[5], 2);
+    }
+}
@@ -0,0 +1,191 @@
+use std::ops::Range;
+
+/// A piecewise-linear map over `i64` that shifts values in given source ranges by a fixed offset and leaves all others
+/// unchanged, as in the seed-to-soil style almanac maps. Maps compose with [`then`](Self::then), so a chain of stages
+/// can be flattened once and then applied to many values or whole intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    // Sorted, non-overlapping, non-empty source ranges with their non-zero offsets
+    segments: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` onto the range starting at `destination`.
+    ///
+    /// # Panics
+    ///
+    /// If `source` overlaps a range already in the map.
+    pub fn insert(&mut self, source: Range<i64>, destination: i64) {
+        if source.is_empty() {
+            return;
+        }
+        let i = self
+            .segments
+            .partition_point(|(r, _)| r.end <= source.start);
+        assert!(
+            self.segments
+                .get(i)
+                .is_none_or(|(r, _)| r.start >= source.end),
+            "{source:?} overlaps a range already in the map"
+        );
+        let offset = destination - source.start;
+        if offset != 0 {
+            self.segments.insert(i, (source, offset));
+        }
+    }
+
+    /// Adds a mapping written as `destination source length`, the almanac's order.
+    pub fn with(mut self, destination: i64, source: i64, len: i64) -> Self {
+        self.insert(source..source + len, destination);
+        self
+    }
+
+    pub fn get(&self, value: i64) -> i64 {
+        let i = self.segments.partition_point(|(r, _)| r.end <= value);
+        match self.segments.get(i) {
+            Some((r, offset)) if r.contains(&value) => value + offset,
+            _ => value,
+        }
+    }
+
+    /// The image of `range` as a sorted list of disjoint ranges.
+    pub fn map_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        let mut image = self
+            .pieces(range)
+            .into_iter()
+            .map(|(r, offset)| r.start + offset..r.end + offset)
+            .collect::<Vec<_>>();
+        image.sort_by_key(|r| r.start);
+        merge(image)
+    }
+
+    /// The map applying `self`, then `next`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut segments = Vec::new();
+        for (piece, offset) in self.pieces(i64::MIN..i64::MAX) {
+            let image = piece.start + offset..piece.end + offset;
+            for (part, next_offset) in next.pieces(image) {
+                segments.push((part.start - offset..part.end - offset, offset + next_offset));
+            }
+        }
+
+        // pieces come out in source order; drop identity stretches and join neighbors that shift alike
+        let mut composed = RangeMap::new();
+        for (range, offset) in segments {
+            if offset == 0 {
+                continue;
+            }
+            match composed.segments.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => composed.segments.push((range, offset)),
+            }
+        }
+        composed
+    }
+
+    /// Composes a chain of maps applied in order into one.
+    pub fn compose<'a>(maps: impl IntoIterator<Item = &'a RangeMap>) -> RangeMap {
+        maps.into_iter()
+            .fold(RangeMap::new(), |composed, map| composed.then(map))
+    }
+
+    /// Splits `range` into consecutive pieces, each with the single offset applied to it (0 outside any segment).
+    fn pieces(&self, range: Range<i64>) -> Vec<(Range<i64>, i64)> {
+        let mut pieces = Vec::new();
+        let mut start = range.start;
+        let first = self.segments.partition_point(|(r, _)| r.end <= start);
+        for (r, offset) in &self.segments[first..] {
+            if start >= range.end || r.start >= range.end {
+                break;
+            }
+            if start < r.start {
+                pieces.push((start..r.start, 0));
+                start = r.start;
+            }
+            let end = r.end.min(range.end);
+            pieces.push((start..end, *offset));
+            start = end;
+        }
+        if start < range.end {
+            pieces.push((start..range.end, 0));
+        }
+        pieces
+    }
+}
+
+/// Merges overlapping or touching ranges in a list sorted by start.
+fn merge(ranges: Vec<Range<i64>>) -> Vec<Range<i64>> {
+    let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023 day 5 example: seed-to-soil, soil-to-fertilizer, fertilizer-to-water
+    fn stages() -> [RangeMap; 3] {
+        [
+            RangeMap::new().with(50, 98, 2).with(52, 50, 48),
+            RangeMap::new()
+                .with(0, 15, 37)
+                .with(37, 52, 2)
+                .with(39, 0, 15),
+            RangeMap::new()
+                .with(49, 53, 8)
+                .with(0, 11, 42)
+                .with(42, 0, 7)
+                .with(57, 7, 4),
+        ]
+    }
+
+    #[test]
+    fn test_get() {
+        let [soil, ..] = stages();
+        assert_eq!(
+            [79, 14, 55, 13, 98, 100].map(|s| soil.get(s)),
+            [81, 14, 57, 13, 50, 100]
+        );
+    }
+
+    #[test]
+    fn test_compose_matches_chained_lookups() {
+        let stages = stages();
+        let composed = RangeMap::compose(&stages);
+        for seed in -5..120 {
+            let expected = stages.iter().fold(seed, |v, map| map.get(v));
+            assert_eq!(composed.get(seed), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_map_range() {
+        let stages = stages();
+        let composed = RangeMap::compose(&stages);
+        let image = composed.map_range(79..93);
+        let mut expected = (79..93)
+            .map(|s| stages.iter().fold(s, |v, map| map.get(v)))
+            .collect::<Vec<_>>();
+        expected.sort();
+        let covered = image.iter().flat_map(|r| r.clone()).collect::<Vec<_>>();
+        assert_eq!(covered, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps")]
+    fn test_overlap() {
+        RangeMap::new().with(0, 10, 5).with(100, 12, 5);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::sequence::{lower_bound, upper_bound};
+use std::ops::{Bound, Deref, RangeBounds};
+
+/// A `Vec` kept in ascending order, for rank and range queries by binary search. Duplicates are kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SortedVec<T>(Vec<T>);
+
+impl<T: Ord> SortedVec<T> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Inserts `value` after any equal values, in O(n).
+    pub fn insert(&mut self, value: T) {
+        let i = upper_bound(&self.0, &value);
+        self.0.insert(i, value);
+    }
+
+    /// Removes one occurrence of `value`, returning whether there was one.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let i = lower_bound(&self.0, value);
+        let found = self.0.get(i) == Some(value);
+        if found {
+            self.0.remove(i);
+        }
+        found
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.0.binary_search(value).is_ok()
+    }
+
+    /// How many values are less than `value`, which is also `value`'s index once deduplicated.
+    pub fn count_below(&self, value: &T) -> usize {
+        lower_bound(&self.0, value)
+    }
+
+    /// How many values are at most `value`.
+    pub fn count_at_most(&self, value: &T) -> usize {
+        upper_bound(&self.0, value)
+    }
+
+    /// The values falling within `range`.
+    pub fn range(&self, range: impl RangeBounds<T>) -> &[T] {
+        let start = match range.start_bound() {
+            Bound::Included(x) => lower_bound(&self.0, x),
+            Bound::Excluded(x) => upper_bound(&self.0, x),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => upper_bound(&self.0, x),
+            Bound::Excluded(x) => lower_bound(&self.0, x),
+            Bound::Unbounded => self.0.len(),
+        };
+        &self.0[start..end.max(start)]
+    }
+
+    /// Removes duplicates, e.g. to turn coordinates into a compressed index with [`Self::count_below`].
+    pub fn dedup(&mut self) {
+        self.0.dedup();
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for SortedVec<T> {
+    fn from(mut values: Vec<T>) -> Self {
+        values.sort();
+        Self(values)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SortedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Deref for SortedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_vec() {
+        let mut values = SortedVec::from(vec![7, 3, 9, 3, 1]);
+        assert_eq!(*values, [1, 3, 3, 7, 9]);
+
+        values.insert(5);
+        assert!(values.remove(&3));
+        assert!(!values.remove(&4));
+        assert_eq!(*values, [1, 3, 5, 7, 9]);
+        assert!(values.contains(&7));
+
+        assert_eq!(values.count_below(&5), 2);
+        assert_eq!(values.count_at_most(&5), 3);
+        assert_eq!(values.range(3..9), [3, 5, 7]);
+        assert_eq!(values.range(4..=9), [5, 7, 9]);
+        assert_eq!(values.range(..), [1, 3, 5, 7, 9]);
+        assert!(values.range(4..5).is_empty());
+    }
+
+    #[test]
+    fn test_coordinate_compression() {
+        let xs = [100, 5, 100, 42];
+        let mut compressed = xs.iter().copied().collect::<SortedVec<_>>();
+        compressed.dedup();
+        let ids = xs.map(|x| compressed.count_below(&x));
+        assert_eq!(ids, [2, 0, 2, 1]);
+    }
+}
@@ -0,0 +1,168 @@
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    terminal: bool,
+}
+
+impl Node {
+    fn child(&self, byte: u8) -> Option<usize> {
+        self.children
+            .iter()
+            .find(|(b, _)| *b == byte)
+            .map(|(_, i)| *i)
+    }
+}
+
+/// A set of byte strings stored as a prefix tree.
+#[derive(Debug, Clone)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    len: usize,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+            len: 0,
+        }
+    }
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct words.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds `word`, returning whether it was newly inserted.
+    pub fn insert(&mut self, word: impl AsRef<[u8]>) -> bool {
+        let mut node = 0;
+        for &byte in word.as_ref() {
+            node = match self.nodes[node].child(byte) {
+                Some(child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.push((byte, child));
+                    child
+                }
+            };
+        }
+
+        let inserted = !self.nodes[node].terminal;
+        self.nodes[node].terminal = true;
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    fn find(&self, s: &[u8]) -> Option<usize> {
+        s.iter()
+            .try_fold(0, |node, &byte| self.nodes[node].child(byte))
+    }
+
+    pub fn contains(&self, word: impl AsRef<[u8]>) -> bool {
+        self.find(word.as_ref())
+            .is_some_and(|node| self.nodes[node].terminal)
+    }
+
+    /// Whether any word starts with `prefix`.
+    pub fn starts_with(&self, prefix: impl AsRef<[u8]>) -> bool {
+        self.find(prefix.as_ref()).is_some()
+    }
+
+    /// Lengths of every word that is a prefix of `s`, shortest first.
+    pub fn prefix_lengths<'a>(&'a self, s: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let mut node = Some(0);
+        (0..=s.len()).filter_map(move |len| {
+            let current = node?;
+            node = s.get(len).and_then(|&byte| self.nodes[current].child(byte));
+            self.nodes[current].terminal.then_some(len)
+        })
+    }
+
+    /// Number of ways `s` can be split into a sequence of words.
+    pub fn count_segmentations(&self, s: impl AsRef<[u8]>) -> u64 {
+        let s = s.as_ref();
+        // ways[i] is the number of segmentations of s[i..]
+        let mut ways = vec![0u64; s.len() + 1];
+        ways[s.len()] = 1;
+        for start in (0..s.len()).rev() {
+            ways[start] = self
+                .prefix_lengths(&s[start..])
+                .filter(|&len| len > 0)
+                .map(|len| ways[start + len])
+                .sum();
+        }
+        ways[0]
+    }
+
+    /// Whether `s` can be split into a sequence of words.
+    pub fn can_segment(&self, s: impl AsRef<[u8]>) -> bool {
+        self.count_segmentations(s) > 0
+    }
+}
+
+impl<W: AsRef<[u8]>> FromIterator<W> for Trie {
+    fn from_iter<I: IntoIterator<Item = W>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        for word in iter {
+            trie.insert(word);
+        }
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn towels() -> Trie {
+        "r, wr, b, g, bwu, rb, gb, br".split(", ").collect()
+    }
+
+    #[test]
+    fn test_trie_lookup() {
+        let mut trie = towels();
+        assert_eq!(trie.len(), 8);
+        assert!(!trie.insert("rb"));
+        assert!(trie.insert("bw"));
+        assert_eq!(trie.len(), 9);
+
+        assert!(trie.contains("bwu"));
+        assert!(trie.contains("bw"));
+        assert!(!trie.contains("w"));
+        assert!(trie.starts_with("w"));
+        assert!(!trie.starts_with("u"));
+        assert!(trie.starts_with(""));
+
+        assert_eq!(
+            trie.prefix_lengths(b"bwurr").collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(trie.prefix_lengths(b"x").count(), 0);
+        assert!(Trie::new().is_empty());
+    }
+
+    #[test]
+    fn test_trie_segmentations() {
+        let trie = towels();
+        let counts = [
+            "brwrr", "bggr", "gbbr", "rrbgbr", "ubwu", "bwurrg", "brgr", "bbrgwb",
+        ]
+        .map(|design| trie.count_segmentations(design));
+
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+        assert!(trie.can_segment("brwrr"));
+        assert!(!trie.can_segment("ubwu"));
+        assert_eq!(trie.count_segmentations(""), 1);
+    }
+}
@@ -0,0 +1,106 @@
+mod permutation;
+
+pub use permutation::Permutation;
+
+/// Every subset of `items`, lazily, each in the original order. Subsets are yielded in binary counting order of
+/// their membership masks, starting with the empty set.
+pub fn subsets<T>(items: &[T]) -> impl Iterator<Item = Vec<&T>> {
+    assert!(items.len() < 64, "too many items to enumerate subsets");
+    (0..1u64 << items.len()).map(move |mask| select(items, mask))
+}
+
+fn select<T>(items: &[T], mask: u64) -> Vec<&T> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Every subset of `items` with exactly `k` elements, lazily, in lexicographic order of positions.
+pub fn subsets_of_size<T>(items: &[T], k: usize) -> impl Iterator<Item = Vec<&T>> {
+    let n = items.len();
+    let mut indices = (k <= n).then(|| (0..k).collect::<Vec<_>>());
+
+    std::iter::from_fn(move || {
+        let current = indices.as_mut()?;
+        let subset = current.iter().map(|&i| &items[i]).collect();
+
+        // advance the rightmost index that still has room, then reset those after it
+        match (0..k).rev().find(|&i| current[i] < n - k + i) {
+            Some(i) => {
+                current[i] += 1;
+                for j in i + 1..k {
+                    current[j] = current[j - 1] + 1;
+                }
+            }
+            None => indices = None,
+        }
+        Some(subset)
+    })
+}
+
+/// Every way to split `items` into two groups, lazily. Each split is yielded once: the first item is always in the
+/// first group, and the second group may be empty.
+pub fn partitions<T>(items: &[T]) -> impl Iterator<Item = (Vec<&T>, Vec<&T>)> {
+    assert!(items.len() <= 64, "too many items to enumerate partitions");
+    let count = 1u64
+        .checked_shl(items.len().saturating_sub(1) as u32)
+        .unwrap_or(0);
+    let all = u64::MAX.checked_shr(64 - items.len() as u32).unwrap_or(0);
+
+    (0..count).map(move |second| {
+        // the first item is bit 0, which is never in the second group
+        let second = second << 1;
+        (select(items, all & !second), select(items, second))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsets() {
+        let all: Vec<Vec<&char>> = subsets(&['a', 'b', 'c']).collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], Vec::<&char>::new());
+        assert_eq!(all[3], [&'a', &'b']);
+        assert_eq!(all[7], [&'a', &'b', &'c']);
+        assert_eq!(subsets::<u8>(&[]).count(), 1);
+
+        // 25 liters of eggnog into the example containers
+        let containers = [20, 15, 10, 5, 5];
+        let ways = subsets(&containers)
+            .filter(|s| s.iter().copied().sum::<u32>() == 25)
+            .count();
+        assert_eq!(ways, 4);
+    }
+
+    #[test]
+    fn test_subsets_of_size() {
+        let pairs: Vec<Vec<&u8>> = subsets_of_size(&[1, 2, 3, 4], 2).collect();
+        assert_eq!(
+            pairs,
+            [[&1, &2], [&1, &3], [&1, &4], [&2, &3], [&2, &4], [&3, &4]]
+        );
+        assert_eq!(subsets_of_size(&[1, 2, 3], 0).count(), 1);
+        assert_eq!(subsets_of_size(&[1, 2, 3], 3).count(), 1);
+        assert_eq!(subsets_of_size(&[1, 2, 3], 4).count(), 0);
+        assert_eq!(subsets_of_size(&[0; 10], 4).count(), 210);
+    }
+
+    #[test]
+    fn test_partitions() {
+        let splits: Vec<_> = partitions(&[1, 2, 3]).collect();
+        assert_eq!(splits.len(), 4);
+        assert_eq!(splits[0], (vec![&1, &2, &3], vec![]));
+        assert!(splits.contains(&(vec![&1, &3], vec![&2])));
+        assert!(splits.iter().all(|(first, _)| first.contains(&&1)));
+
+        assert_eq!(partitions(&[1]).collect::<Vec<_>>(), [(vec![&1], vec![])]);
+        assert_eq!(partitions::<u8>(&[]).count(), 1);
+        assert_eq!(partitions(&[0; 10]).count(), 512);
+    }
+}
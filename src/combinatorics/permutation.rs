@@ -0,0 +1,153 @@
+use crate::error::{Error, Result};
+use crate::math::least_common_multiple;
+
+/// A rearrangement of `n` positions: applying it to a sequence moves the item at position `p[i]` to position `i`.
+/// Repeating a permutation a billion times only needs its cycles, since each cycle returns to the start after as
+/// many steps as it is long.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    pub fn identity(n: usize) -> Self {
+        Self((0..n).collect())
+    }
+
+    /// The permutation that turns `before` into `after`, which must hold the same distinct items.
+    pub fn between<T: PartialEq>(before: &[T], after: &[T]) -> Result<Self> {
+        let sources = after
+            .iter()
+            .map(|item| before.iter().position(|b| b == item))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| Error::parse("Sequences don't hold the same items"))?;
+        Self::try_from(sources)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The source position for each position.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Rearranges `items`, which must have [`len`](Self::len) elements.
+    pub fn apply<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        assert_eq!(
+            items.len(),
+            self.len(),
+            "permutation and items differ in length"
+        );
+        self.0.iter().map(|&i| items[i].clone()).collect()
+    }
+
+    /// The permutation applying `self`, then `next`.
+    pub fn then(&self, next: &Permutation) -> Permutation {
+        Permutation(next.apply(&self.0))
+    }
+
+    pub fn inverse(&self) -> Permutation {
+        let mut inverse = vec![0; self.len()];
+        for (i, &source) in self.0.iter().enumerate() {
+            inverse[source] = i;
+        }
+        Permutation(inverse)
+    }
+
+    /// The cycles of positions, each starting at its smallest position, in order of that position. Fixed points are
+    /// cycles of length 1.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut i = start;
+            while !seen[i] {
+                seen[i] = true;
+                cycle.push(i);
+                i = self.0[i];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// How many applications return every position to where it started: the LCM of the cycle lengths.
+    pub fn order(&self) -> u64 {
+        self.cycles()
+            .iter()
+            .fold(1, |order, c| least_common_multiple(order, c.len() as u64))
+    }
+
+    /// The permutation applied `k` times in a row, computed from the cycles in `O(n)`.
+    pub fn pow(&self, k: u64) -> Permutation {
+        let mut result = vec![0; self.len()];
+        for cycle in self.cycles() {
+            let shift = (k % cycle.len() as u64) as usize;
+            for (j, &position) in cycle.iter().enumerate() {
+                result[position] = cycle[(j + shift) % cycle.len()];
+            }
+        }
+        Permutation(result)
+    }
+}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = Error;
+
+    /// Each position's source position; every position must appear exactly once.
+    fn try_from(sources: Vec<usize>) -> Result<Self> {
+        let mut seen = vec![false; sources.len()];
+        for &i in &sources {
+            if i >= sources.len() || std::mem::replace(&mut seen[i], true) {
+                return Err(Error::parse(format!("{sources:?} is not a permutation")));
+            }
+        }
+        Ok(Self(sources))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_apply_and_compose() -> Result<()> {
+        // 2017 day 16: s1, x3/4 on "abcde" gives "eabdc"
+        let line = b"abcde";
+        let spin = Permutation::try_from(vec![4, 0, 1, 2, 3])?;
+        let exchange = Permutation::try_from(vec![0, 1, 2, 4, 3])?;
+        let dance = spin.then(&exchange);
+        assert_eq!(dance.apply(line), b"eabdc");
+        assert_eq!(Permutation::between(line, b"eabdc")?, dance);
+
+        assert_eq!(dance.then(&dance.inverse()), Permutation::identity(5));
+        assert!(Permutation::try_from(vec![0, 0]).is_err());
+        assert!(Permutation::try_from(vec![2, 0]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pow() -> Result<()> {
+        let p = Permutation::try_from(vec![1, 2, 0, 4, 3, 5])?;
+        assert_eq!(p.cycles(), [vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(p.order(), 6);
+
+        let items = ['a', 'b', 'c', 'd', 'e', 'f'];
+        let mut repeated = items.to_vec();
+        for k in 0..20 {
+            assert_eq!(p.pow(k).apply(&items), repeated, "k = {k}");
+            repeated = p.apply(&repeated);
+        }
+        assert_eq!(p.pow(1_000_000_000), p.pow(1_000_000_000 % 6));
+        Ok(())
+    }
+}
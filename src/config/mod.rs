@@ -0,0 +1,136 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const YEAR_ENV: &str = "AOC_YEAR";
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "input";
+
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub session: Option<String>,
+    pub year: Option<u16>,
+    pub input_dir: Option<PathBuf>,
+}
+
+/// Redacts the session cookie, so logging a `Config` (or a `RunContext` holding one) doesn't leak it.
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("session", &self.session.as_ref().map(|_| "<redacted>"))
+            .field("year", &self.year)
+            .field("input_dir", &self.input_dir)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Loads `~/.config/aoc/config.toml` (or `$XDG_CONFIG_HOME/aoc/config.toml`), if present.
+    pub fn load() -> Result<Self> {
+        match config_path() {
+            Some(path) if path.is_file() => Self::parse(&fs::read_to_string(path)?),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Reads `AOC_SESSION`, `AOC_YEAR`, and `AOC_INPUT_DIR` from the environment.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            session: env::var(SESSION_ENV).ok(),
+            year: env::var(YEAR_ENV).ok().map(|y| y.parse()).transpose()?,
+            input_dir: env::var_os(INPUT_DIR_ENV).map(PathBuf::from),
+        })
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Merges `other` over `self`, preferring values that are set in `other`.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            session: other.session.or(self.session),
+            year: other.year.or(self.year),
+            input_dir: other.input_dir.or(self.input_dir),
+        }
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        self.input_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+    }
+}
+
+/// Directory for cached responses from adventofcode.com, e.g. `~/.cache/aoc`.
+pub fn cache_dir() -> PathBuf {
+    match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".cache"))
+            .unwrap_or_else(env::temp_dir),
+    }
+    .join("aoc")
+}
+
+fn config_path() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("aoc").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let config = Config::parse("session = \"abc123\"\nyear = 2024\n")?;
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.year, Some(2024));
+        assert_eq!(config.input_dir, None);
+        assert_eq!(config.input_dir(), PathBuf::from("input"));
+
+        assert_eq!(Config::parse("")?, Config::default());
+        assert!(Config::parse("year = \"twenty\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_redacts_session() -> Result<()> {
+        let config = Config::parse("session = \"abc123\"\nyear = 2024\n")?;
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("abc123"));
+        assert!(debug.contains("<redacted>") && debug.contains("2024"));
+        assert!(format!("{:?}", Config::default()).contains("session: None"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() {
+        let file = Config {
+            session: Some("from-file".to_string()),
+            year: Some(2023),
+            input_dir: Some(PathBuf::from("data")),
+        };
+        let cli = Config {
+            session: None,
+            year: Some(2024),
+            input_dir: None,
+        };
+
+        let merged = file.merge(cli);
+        assert_eq!(merged.session.as_deref(), Some("from-file"));
+        assert_eq!(merged.year, Some(2024));
+        assert_eq!(merged.input_dir(), PathBuf::from("data"));
+    }
+}
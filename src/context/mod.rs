@@ -0,0 +1,443 @@
+use crate::Input;
+use crate::answer::Answer;
+use crate::config::Config;
+use crate::logging::HumanDuration;
+use crate::solver::Solver;
+use crate::viz::Viz;
+use anyhow::{Result, bail};
+use log::info;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::time::Instant;
+
+#[derive(Debug, Clone)]
+pub struct RunContext {
+    pub lines: Vec<String>,
+    pub input: Input,
+    pub verbose: bool,
+    /// Print only answers, to stdout, instead of logging
+    pub quiet: bool,
+    pub part: Option<u8>,
+    pub day: Option<u8>,
+    pub year: Option<u16>,
+    pub config: Config,
+    pub viz: Viz,
+    /// Every example input, when running with `--all-examples`. `lines` is then empty; each example's lines are in a
+    /// context of its own from [`for_example`](Self::for_example).
+    pub examples: Vec<Example>,
+    /// Confirmed answer for each part of the actual input, from `input.answers`; empty with `--force`
+    pub recorded: Vec<Option<String>>,
+}
+
+/// An example input along with its expected answers, if known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub lines: Vec<String>,
+    /// Expected answer for each part, in order
+    pub expected: Vec<Option<String>>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        usize::from(part)
+            .checked_sub(1)
+            .and_then(|i| self.expected.get(i))?
+            .as_deref()
+    }
+}
+
+impl RunContext {
+    pub fn is_test(&self) -> bool {
+        self.input == Input::Test
+    }
+
+    /// Picks between an example-specific and an actual-input-specific parameter.
+    pub fn pick<T>(&self, test: T, actual: T) -> T {
+        match self.input {
+            Input::Test => test,
+            Input::Actual => actual,
+        }
+    }
+
+    /// The input lines from last to first, for inputs stated bottom-up.
+    pub fn lines_reversed(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().rev().map(String::as_str)
+    }
+
+    /// A context for running against `example` alone: its lines, as test input, with no examples or recorded answers.
+    pub fn for_example(&self, example: &Example) -> RunContext {
+        RunContext {
+            lines: example.lines.clone(),
+            input: Input::Test,
+            verbose: self.verbose,
+            quiet: self.quiet,
+            part: self.part,
+            day: self.day,
+            year: self.year,
+            config: self.config.clone(),
+            viz: self.viz,
+            examples: Vec::new(),
+            recorded: Vec::new(),
+        }
+    }
+
+    /// Whether `part` should run, given any `--part` selection.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// The confirmed answer for `part`, if one is recorded and `--force` wasn't given.
+    pub fn recorded(&self, part: u8) -> Option<&str> {
+        usize::from(part)
+            .checked_sub(1)
+            .and_then(|i| self.recorded.get(i))?
+            .as_deref()
+    }
+
+    /// Reports an answer: logged at info level, or printed alone on stdout in quiet mode.
+    pub fn answer(&self, part: u8, answer: impl Display) {
+        if self.quiet {
+            println!("{}", answer.to_string().trim_start_matches('\n'));
+        } else {
+            info!("Part {part}: {answer}");
+        }
+    }
+
+    /// Runs each selected part of `solver` against this context's input, reporting each answer along with how long
+    /// it took and the process's peak memory use so far. Parts with a [`recorded`](Self::recorded) answer are
+    /// reported without being recomputed.
+    ///
+    /// With `--all-examples`, calls [`run_examples`](Self::run_examples) instead and returns no answers; call that
+    /// directly for each example's answers.
+    pub fn run<S: Solver>(&self, solver: &S) -> Result<Vec<Answer>> {
+        if !self.examples.is_empty() {
+            self.run_examples(solver)?;
+            return Ok(Vec::new());
+        }
+
+        let mut answers = Vec::new();
+        for part in [1, 2].into_iter().filter(|&p| self.runs_part(p)) {
+            if let Some(recorded) = self.recorded(part) {
+                if self.quiet {
+                    self.answer(part, recorded);
+                } else {
+                    info!("Part {part}: {recorded} [recorded]");
+                }
+                answers.push(Answer::from(recorded));
+                continue;
+            }
+
+            let start = Instant::now();
+            let answer = solver.solve(part, &self.lines)?;
+            let elapsed = HumanDuration(start.elapsed());
+
+            match (self.quiet, peak_rss()) {
+                (true, _) => self.answer(part, &answer),
+                (false, Some(bytes)) => info!(
+                    "Part {part}: {answer} [{elapsed}, peak RSS {:.1} MiB]",
+                    bytes as f64 / (1024.0 * 1024.0)
+                ),
+                (false, None) => info!("Part {part}: {answer} [{elapsed}]"),
+            }
+            answers.push(answer);
+        }
+        Ok(answers)
+    }
+
+    /// Runs each selected part of `solver` against every example, each in its own
+    /// [`for_example`](Self::for_example) context, and prints a table comparing each answer with the expected one.
+    /// Returns each example's name with its answers, in part order, or fails if any answer is wrong or errors.
+    pub fn run_examples<S: Solver>(&self, solver: &S) -> Result<Vec<(String, Vec<Answer>)>> {
+        let mut rows = Vec::new();
+        let mut results = Vec::new();
+        for example in &self.examples {
+            let ctx = self.for_example(example);
+            let mut answers = Vec::new();
+            for part in [1, 2].into_iter().filter(|&p| ctx.runs_part(p)) {
+                let expected = example.expected(part);
+                let (answer, status) = match solver.solve(part, &ctx.lines) {
+                    Ok(answer) => {
+                        let status = match expected {
+                            Some(e) if answer.matches(e) => Status::Ok,
+                            Some(_) => Status::Mismatch,
+                            None => Status::Unknown,
+                        };
+                        let shown = answer.to_string();
+                        answers.push(answer);
+                        (shown, status)
+                    }
+                    Err(e) => (format!("error: {e:#}"), Status::Error),
+                };
+                rows.push(ExampleRow {
+                    name: &example.name,
+                    part,
+                    answer,
+                    expected: expected.unwrap_or("?"),
+                    status,
+                });
+            }
+            results.push((example.name.clone(), answers));
+        }
+
+        print!("{}", example_table(&rows));
+        let failed = rows
+            .iter()
+            .filter(|r| matches!(r.status, Status::Mismatch | Status::Error))
+            .count();
+        if failed > 0 {
+            bail!("{failed} of {} example answers failed", rows.len());
+        }
+        Ok(results)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Mismatch,
+    Error,
+    Unknown,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Ok => "ok",
+            Status::Mismatch => "MISMATCH",
+            Status::Error => "ERROR",
+            Status::Unknown => "?",
+        })
+    }
+}
+
+struct ExampleRow<'a> {
+    name: &'a str,
+    part: u8,
+    answer: String,
+    expected: &'a str,
+    status: Status,
+}
+
+/// Renders the `--all-examples` comparison, one row per example and part, with multi-line (grid) answers collapsed
+/// onto one line.
+fn example_table(rows: &[ExampleRow]) -> String {
+    let cells = rows
+        .iter()
+        .map(|r| {
+            [
+                r.name.to_string(),
+                r.part.to_string(),
+                r.answer.trim().replace('\n', " / "),
+                r.expected.to_string(),
+                r.status.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = ["example", "part", "answer", "expected", "status"].map(String::from);
+    let widths = (0..header.len())
+        .map(|i| {
+            cells
+                .iter()
+                .chain([&header])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&cells) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Peak resident set size of this process in bytes, where the platform makes it available.
+fn peak_rss() -> Option<u64> {
+    if cfg!(target_os = "linux") {
+        parse_peak_rss(&fs::read_to_string("/proc/self/status").ok()?)
+    } else {
+        None
+    }
+}
+
+/// Reads the `VmHWM` ("high water mark") line of `/proc/self/status`.
+fn parse_peak_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Parses the day from the running binary's name, e.g. `day07` or `2024-07`.
+pub(crate) fn current_day() -> Option<u8> {
+    let exe = env::current_exe().ok()?;
+    day_from_name(exe.file_stem()?.to_str()?)
+}
+
+fn day_from_name(name: &str) -> Option<u8> {
+    let digits = name
+        .chars()
+        .rev()
+        .take_while(char::is_ascii_digit)
+        .collect::<Vec<_>>();
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+
+    let day = digits.into_iter().rev().collect::<String>().parse().ok()?;
+    (1..=25).contains(&day).then_some(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_peak_rss() {
+        let status =
+            "Name:\tday07\nVmPeak:\t  10000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+        assert_eq!(parse_peak_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_peak_rss("Name:\tday07\n"), None);
+    }
+
+    #[test]
+    fn test_day_from_name() {
+        assert_eq!(day_from_name("day07"), Some(7));
+        assert_eq!(day_from_name("day25"), Some(25));
+        assert_eq!(day_from_name("2024-12"), Some(12));
+        assert_eq!(day_from_name("day26"), None);
+        assert_eq!(day_from_name("day"), None);
+        assert_eq!(day_from_name("solver2024"), None);
+    }
+
+    #[test]
+    fn test_run_skips_recorded_parts() {
+        struct Counter;
+
+        impl Solver for Counter {
+            fn part1(&self, _: &[String]) -> Result<Answer> {
+                panic!("part 1 is recorded");
+            }
+
+            fn part2(&self, lines: &[String]) -> Result<Answer> {
+                Ok(lines.len().into())
+            }
+        }
+
+        let ctx = RunContext {
+            lines: vec!["a".into(), "b".into()],
+            input: Input::Actual,
+            verbose: false,
+            quiet: false,
+            part: None,
+            day: None,
+            year: None,
+            config: Config::default(),
+            viz: Viz::new(false),
+            examples: Vec::new(),
+            recorded: vec![Some("142".into()), None],
+        };
+        assert_eq!(ctx.recorded(1), Some("142"));
+        assert_eq!(ctx.recorded(2), None);
+        assert_eq!(ctx.recorded(0), None);
+        assert_eq!(
+            ctx.run(&Counter).unwrap(),
+            [Answer::from("142"), Answer::from(2usize)]
+        );
+    }
+
+    #[test]
+    fn test_run_examples_per_example() {
+        struct Lengths;
+
+        impl Solver for Lengths {
+            fn part1(&self, lines: &[String]) -> Result<Answer> {
+                Ok(lines.len().into())
+            }
+
+            fn part2(&self, lines: &[String]) -> Result<Answer> {
+                Ok(lines.concat().into())
+            }
+        }
+
+        let example = |name: &str, lines: &[&str], expected: &[&str]| Example {
+            name: name.into(),
+            lines: lines.iter().map(|&l| l.into()).collect(),
+            expected: expected.iter().map(|&e| Some(e.into())).collect(),
+        };
+        let ctx = RunContext {
+            lines: Vec::new(),
+            input: Input::Test,
+            verbose: false,
+            quiet: true,
+            part: None,
+            day: None,
+            year: None,
+            config: Config::default(),
+            viz: Viz::new(false),
+            examples: vec![
+                example("example", &["a", "b"], &["2", "ab"]),
+                example("example2", &["c"], &["1", "c"]),
+            ],
+            recorded: vec![Some("142".into())],
+        };
+
+        assert_eq!(ctx.examples[1].expected(2), Some("c"));
+        assert_eq!(ctx.examples[1].expected(0), None);
+        assert_eq!(ctx.examples[1].expected(3), None);
+
+        let single = ctx.for_example(&ctx.examples[1]);
+        assert_eq!(single.lines, ["c"]);
+        assert!(single.examples.is_empty() && single.recorded.is_empty());
+
+        assert_eq!(
+            ctx.run_examples(&Lengths).unwrap(),
+            [
+                (
+                    "example".to_string(),
+                    vec![Answer::from(2usize), Answer::from("ab")]
+                ),
+                (
+                    "example2".to_string(),
+                    vec![Answer::from(1usize), Answer::from("c")]
+                ),
+            ]
+        );
+        assert!(ctx.run(&Lengths).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_example_table() {
+        let rows = [
+            ExampleRow {
+                name: "example",
+                part: 1,
+                answer: "142".into(),
+                expected: "142",
+                status: Status::Ok,
+            },
+            ExampleRow {
+                name: "example2",
+                part: 2,
+                answer: "280".into(),
+                expected: "281",
+                status: Status::Mismatch,
+            },
+        ];
+        assert_eq!(
+            example_table(&rows),
+            "example   part  answer  expected  status\n\
+             example   1     142     142       ok\n\
+             example2  2     280     281       MISMATCH\n"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Errors returned by the library modules, so callers can match on what went wrong. Binaries can keep using `anyhow`:
+/// `?` converts any of these into an `anyhow::Error`, and `downcast_ref::<Error>()` recovers it.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Text that couldn't be parsed. `line` and `column` are 1-based; `text` is the offending line, shown with a caret
+    /// under `column`.
+    Parse {
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+        text: Option<String>,
+    },
+    /// An input that couldn't be opened or read.
+    #[cfg(feature = "std")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Grid rows of different lengths.
+    GridShape {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A grid whose `(width, height)` doesn't match the size required.
+    GridSize {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+impl Error {
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            line: None,
+            column: None,
+            text: None,
+        }
+    }
+
+    /// Records the 1-based line a parse error came from, for parsers that only see one line at a time.
+    pub fn at_line(mut self, number: usize) -> Self {
+        if let Self::Parse { line, .. } = &mut self {
+            *line = Some(number);
+        }
+        self
+    }
+
+    /// Records the 1-based column a parse error points at, along with the text it points into.
+    pub fn at_column(mut self, number: usize, source: impl Into<String>) -> Self {
+        if let Self::Parse { column, text, .. } = &mut self {
+            *column = Some(number);
+            *text = Some(source.into());
+        }
+        self
+    }
+}
+
+/// The 1-based column at which `part`, a slice of `line`, starts.
+pub(crate) fn column_of(line: &str, part: &str) -> Option<usize> {
+    let offset = (part.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset + part.len() > line.len() {
+        return None;
+    }
+    Some(line.get(..offset)?.chars().count() + 1)
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse {
+                message,
+                line,
+                column,
+                text,
+            } => {
+                write!(f, "{message}")?;
+                match (line, column) {
+                    (Some(line), Some(column)) => write!(f, " at line {line}, column {column}")?,
+                    (Some(line), None) => write!(f, " at line {line}")?,
+                    (None, Some(column)) => write!(f, " at column {column}")?,
+                    (None, None) => (),
+                }
+                if let (Some(text), Some(column)) = (text, column) {
+                    write!(f, ":\n{text}\n{:>column$}", "^")?;
+                }
+                Ok(())
+            }
+            #[cfg(feature = "std")]
+            Self::Io { path, source } => write!(f, "Can't read {}: {source}", path.display()),
+            Self::GridShape {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Rows must be the same length: row {row} has {found} cells, expected {expected}"
+            ),
+            Self::GridSize { expected, found } => write!(
+                f,
+                "Expected a {}x{} grid, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+        }
+    }
+}
+
+/// The standard library's parse errors become [`Error::Parse`], so the parsers generic over `FromStr` can require
+/// `T::Err: Into<Error>` and keep the column of a field type that reports one.
+macro_rules! impl_from_parse_error {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Error {
+                fn from(err: $t) -> Self {
+                    Self::parse(err.to_string())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_parse_error!(
+    core::num::ParseIntError,
+    core::num::ParseFloatError,
+    core::str::ParseBoolError,
+    core::char::ParseCharError
+);
+
+/// For `String`, whose `FromStr` can't fail.
+impl From<core::convert::Infallible> for Error {
+    fn from(err: core::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_display() {
+        assert_eq!(Error::parse("Bad value").to_string(), "Bad value");
+        assert_eq!(
+            Error::parse("Bad value").at_line(4).to_string(),
+            "Bad value at line 4"
+        );
+
+        let err = Error::Parse {
+            message: "Expected digit".into(),
+            line: None,
+            column: Some(3),
+            text: Some("ab?".into()),
+        };
+        assert_eq!(err.to_string(), "Expected digit at column 3:\nab?\n  ^");
+        assert_eq!(
+            err.at_line(2).to_string(),
+            "Expected digit at line 2, column 3:\nab?\n  ^"
+        );
+    }
+
+    #[test]
+    fn test_at_column() {
+        let line = "1,2,x";
+        let column = column_of(line, &line[4..]).unwrap();
+        assert_eq!(column, 5);
+        assert_eq!(
+            Error::parse("Bad value")
+                .at_column(column, line)
+                .to_string(),
+            "Bad value at column 5:\n1,2,x\n    ^"
+        );
+        assert_eq!(column_of(&line[..3], &line[2..]), None);
+    }
+
+    #[test]
+    fn test_into_anyhow() {
+        let err: anyhow::Error = Error::GridShape {
+            row: 1,
+            expected: 3,
+            found: 2,
+        }
+        .into();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::GridShape { row: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,222 @@
+use super::Graph;
+
+/// Fixed-capacity bitset over node indices.
+#[derive(Clone)]
+struct NodeSet(Vec<u64>);
+
+impl NodeSet {
+    fn empty(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn full(len: usize) -> Self {
+        let mut set = Self::empty(len);
+        for i in 0..len {
+            set.insert(i);
+        }
+        set
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn intersection_len(&self, other: &Self) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(w, &word)| {
+            (0..64)
+                .filter(move |b| word & (1 << b) != 0)
+                .map(move |b| w * 64 + b)
+        })
+    }
+}
+
+struct BronKerbosch {
+    neighbors: Vec<NodeSet>,
+    /// Size a clique must exceed to be worth reporting
+    bound: usize,
+}
+
+impl BronKerbosch {
+    fn new<N, E>(graph: &Graph<N, E>) -> Self {
+        let neighbors = (0..graph.len())
+            .map(|i| {
+                let mut set = NodeSet::empty(graph.len());
+                for (j, _) in graph.neighbors(i) {
+                    if i != j {
+                        set.insert(j);
+                    }
+                }
+                set
+            })
+            .collect();
+        Self {
+            neighbors,
+            bound: 0,
+        }
+    }
+
+    /// Pivoting Bron–Kerbosch. `visit` is called for each maximal clique found and returns the size a clique must
+    /// exceed to be worth reporting, which allows branches that can't beat it to be pruned.
+    fn search<F>(&mut self, clique: &mut Vec<usize>, mut p: NodeSet, mut x: NodeSet, visit: &mut F)
+    where
+        F: FnMut(&[usize]) -> usize,
+    {
+        if p.is_empty() {
+            if x.is_empty() && clique.len() > self.bound {
+                self.bound = visit(clique);
+            }
+            return;
+        }
+
+        if self.bound > 0 && clique.len() + p.len() <= self.bound {
+            return;
+        }
+
+        let pivot = p
+            .iter()
+            .chain(x.iter())
+            .max_by_key(|&u| p.intersection_len(&self.neighbors[u]))
+            .unwrap_or_default();
+
+        for v in p
+            .difference(&self.neighbors[pivot])
+            .iter()
+            .collect::<Vec<_>>()
+        {
+            clique.push(v);
+            let next_p = p.intersection(&self.neighbors[v]);
+            let next_x = x.intersection(&self.neighbors[v]);
+            self.search(clique, next_p, next_x, visit);
+            clique.pop();
+            p.remove(v);
+            x.insert(v);
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// All maximal cliques of an undirected graph, each as sorted node indices.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = Vec::new();
+        BronKerbosch::new(self).search(
+            &mut Vec::new(),
+            NodeSet::full(self.len()),
+            NodeSet::empty(self.len()),
+            &mut |clique: &[usize]| {
+                let mut clique = clique.to_vec();
+                clique.sort_unstable();
+                cliques.push(clique);
+                0
+            },
+        );
+        cliques
+    }
+
+    /// A largest clique of an undirected graph, as sorted node indices.
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        let mut best = Vec::new();
+        BronKerbosch::new(self).search(
+            &mut Vec::new(),
+            NodeSet::full(self.len()),
+            NodeSet::empty(self.len()),
+            &mut |clique: &[usize]| {
+                if clique.len() > best.len() {
+                    best = clique.to_vec();
+                }
+                best.len()
+            },
+        );
+        best.sort_unstable();
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lan_party() -> Graph<&'static str> {
+        let input = "kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq tb-ka wh-tc yn-cg kh-ub ta-co de-co tc-td \
+                     tb-wq wh-td ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq wq-vc wh-yn ka-de kh-ta co-tc wh-qp \
+                     tb-vc td-yn";
+        input
+            .split_whitespace()
+            .map(|pair| pair.split_once('-').unwrap())
+            .collect()
+    }
+
+    fn names(graph: &Graph<&'static str>, clique: &[usize]) -> Vec<&'static str> {
+        let mut names = clique.iter().map(|&i| *graph.node(i)).collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_maximum_clique() {
+        let graph = lan_party();
+        let clique = graph.maximum_clique();
+        assert_eq!(names(&graph, &clique).join(","), "co,de,ka,ta");
+
+        assert!(Graph::<u8>::undirected().maximum_clique().is_empty());
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        // Two triangles sharing an edge, plus a pendant node
+        let graph = [(1, 2), (2, 3), (1, 3), (2, 4), (3, 4), (4, 5)]
+            .into_iter()
+            .collect::<Graph<u8>>();
+
+        let mut cliques = graph
+            .maximal_cliques()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| *graph.node(i)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        cliques.iter_mut().for_each(|c| c.sort_unstable());
+        cliques.sort();
+
+        assert_eq!(cliques, vec![vec![1, 2, 3], vec![2, 3, 4], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_maximal_cliques_large() {
+        // 100 nodes with edges between numbers sharing a residue mod 5: five disjoint 20-cliques
+        let graph = (0..100u32)
+            .flat_map(|a| ((a + 1)..100).map(move |b| (a, b)))
+            .filter(|(a, b)| a % 5 == b % 5)
+            .collect::<Graph<u32>>();
+
+        let cliques = graph.maximal_cliques();
+        assert_eq!(cliques.len(), 5);
+        assert!(cliques.iter().all(|c| c.len() == 20));
+        assert_eq!(graph.maximum_clique().len(), 20);
+    }
+}
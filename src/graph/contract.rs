@@ -0,0 +1,114 @@
+use super::Graph;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+impl<N: Clone + Eq + Hash> Graph<N, u64> {
+    /// Collapses corridors — chains of nodes with exactly two distinct neighbors — into single weighted edges between
+    /// the remaining junctions, summing the weights along each chain. Nodes for which `keep` returns true (typically
+    /// the start and goal) are never collapsed.
+    ///
+    /// Parallel corridors between the same junctions become parallel edges. Corridors that dead-end, can't be
+    /// traversed in a directed graph, or loop without reaching a junction are dropped. Junctions keep their relative
+    /// order.
+    pub fn contract_corridors(&self, mut keep: impl FnMut(&N) -> bool) -> Graph<N, u64> {
+        let mut linked: Vec<HashSet<usize>> = vec![HashSet::new(); self.len()];
+        for (from, to, _) in self.edges() {
+            if from != to {
+                linked[from].insert(to);
+                linked[to].insert(from);
+            }
+        }
+        let junction: Vec<bool> = (0..self.len())
+            .map(|i| keep(&self.nodes[i]) || linked[i].len() != 2)
+            .collect();
+
+        let mut contracted = Graph::new(self.directed);
+        for i in (0..self.len()).filter(|&i| junction[i]) {
+            contracted.add_node(self.nodes[i].clone());
+        }
+
+        for from in (0..self.len()).filter(|&i| junction[i]) {
+            for (first, &weight) in self.neighbors(from) {
+                let (mut previous, mut current, mut total) = (from, first, weight);
+                while !junction[current] {
+                    let Some((next, &w)) = self.neighbors(current).find(|&(n, _)| n != previous)
+                    else {
+                        break;
+                    };
+                    (previous, current, total) = (current, next, total + w);
+                }
+
+                // undirected corridors are found from both ends, so only add them from one
+                if junction[current] && current != from && (self.directed || from < current) {
+                    contracted.add_edge(
+                        self.nodes[from].clone(),
+                        self.nodes[current].clone(),
+                        total,
+                    );
+                }
+            }
+        }
+        contracted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_corridors() {
+        // a - b - c - d, with a spur c - e
+        let mut graph = Graph::undirected();
+        for (from, to, w) in [
+            ('a', 'b', 1),
+            ('b', 'c', 2),
+            ('c', 'd', 3),
+            ('c', 'e', 4),
+            ('d', 'f', 5),
+        ] {
+            graph.add_edge(from, to, w);
+        }
+
+        let contracted = graph.contract_corridors(|_| false);
+        assert_eq!(contracted.nodes(), ['a', 'c', 'e', 'f']);
+        assert_eq!(contracted.edge_count(), 3);
+        assert_eq!(
+            contracted
+                .neighbors_of(&'c')
+                .map(|(&n, &w)| (n, w))
+                .collect::<Vec<_>>(),
+            [('a', 3), ('f', 8), ('e', 4)]
+        );
+
+        let kept = graph.contract_corridors(|&n| n == 'd');
+        assert!(kept.has_edge(&'d', &'f'));
+        assert_eq!(kept.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_contract_corridors_directed() {
+        // two one-way routes from s to t, one of them blocked halfway
+        let mut graph = Graph::directed();
+        for (from, to) in [
+            ('s', 'a'),
+            ('a', 'b'),
+            ('b', 't'),
+            ('s', 'x'),
+            ('y', 'x'),
+            ('y', 't'),
+        ] {
+            graph.add_edge(from, to, 1);
+        }
+
+        let contracted = graph.contract_corridors(|&n| n == 's' || n == 't');
+        assert_eq!(contracted.edge_count(), 1);
+        assert_eq!(
+            contracted
+                .neighbors_of(&'s')
+                .map(|(&n, &w)| (n, w))
+                .collect::<Vec<_>>(),
+            [('t', 3)]
+        );
+    }
+}
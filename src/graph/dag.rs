@@ -0,0 +1,251 @@
+use super::Graph;
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Folds values over a DAG in post-order, memoizing each node's value. A goal node's value is `goal`, and any other
+/// node's value is `empty` combined with the value of each of its successors along the connecting edge.
+fn fold<N, C, I, FS, FG, V, FC>(
+    start: N,
+    mut successors: FS,
+    mut is_goal: FG,
+    goal: V,
+    empty: V,
+    mut combine: FC,
+) -> Result<(V, HashMap<N, Option<V>>)>
+where
+    N: Clone + Eq + Hash,
+    FS: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+    V: Clone,
+    FC: FnMut(V, &C, &V) -> V,
+{
+    // None marks a node whose successors are still being explored
+    let mut memo: HashMap<N, Option<V>> = HashMap::new();
+
+    if is_goal(&start) {
+        memo.insert(start, Some(goal.clone()));
+        return Ok((goal, memo));
+    }
+
+    memo.insert(start.clone(), None);
+    let children = successors(&start).into_iter();
+    let mut stack: Vec<(N, I::IntoIter, V, Option<C>)> =
+        vec![(start, children, empty.clone(), None)];
+
+    while let Some((_, children, acc, _)) = stack.last_mut() {
+        match children.next() {
+            Some((next, cost)) => match memo.get(&next) {
+                Some(Some(value)) => *acc = combine(acc.clone(), &cost, value),
+                Some(None) => bail!("Graph contains a cycle"),
+                None => {
+                    if is_goal(&next) {
+                        *acc = combine(acc.clone(), &cost, &goal);
+                        memo.insert(next, Some(goal.clone()));
+                    } else {
+                        memo.insert(next.clone(), None);
+                        let children = successors(&next).into_iter();
+                        stack.push((next, children, empty.clone(), Some(cost)));
+                    }
+                }
+            },
+            None => {
+                let Some((node, _, value, cost)) = stack.pop() else {
+                    break;
+                };
+                memo.insert(node, Some(value.clone()));
+                match (stack.last_mut(), cost) {
+                    (Some((_, _, parent_acc, _)), Some(cost)) => {
+                        *parent_acc = combine(parent_acc.clone(), &cost, &value);
+                    }
+                    _ => return Ok((value, memo)),
+                }
+            }
+        }
+    }
+
+    unreachable!("the start node's frame always returns")
+}
+
+/// Counts the distinct paths from `start` to any node satisfying `is_goal`. Paths end at the first goal they reach.
+/// Fails if a cycle is reachable from `start`.
+pub fn count_paths<N, I, FS, FG>(start: N, mut successors: FS, is_goal: FG) -> Result<u64>
+where
+    N: Clone + Eq + Hash,
+    FS: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let (count, _) = fold(
+        start,
+        |n| successors(n).into_iter().map(|n| (n, ())),
+        is_goal,
+        1u64,
+        0,
+        |acc, _, child| acc + child,
+    )?;
+    Ok(count)
+}
+
+/// Longest path from `start` to any node satisfying `is_goal`, where `successors` yields each neighbor along with
+/// the cost of the edge to it. Returns the total cost and the path, or `None` if no goal is reachable. Fails if a cycle
+/// is reachable from `start`.
+pub fn longest_path_dag<N, I, FS, FG>(
+    start: N,
+    mut successors: FS,
+    mut is_goal: FG,
+) -> Result<Option<(u64, Vec<N>)>>
+where
+    N: Clone + Eq + Hash,
+    FS: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    FG: FnMut(&N) -> bool,
+{
+    let (best, memo) = fold(
+        start.clone(),
+        &mut successors,
+        &mut is_goal,
+        Some(0u64),
+        None,
+        |acc, cost, child| acc.max(child.map(|c| c + cost)),
+    )?;
+    let Some(best) = best else {
+        return Ok(None);
+    };
+
+    // Walk forward along edges that account for each node's memoized best
+    let mut path = vec![start.clone()];
+    let mut node = start;
+    let mut remaining = best;
+    while !is_goal(&node) {
+        let Some((next, cost)) = successors(&node).into_iter().find(
+            |(next, cost)| matches!(memo.get(next), Some(Some(Some(v))) if v + cost == remaining),
+        ) else {
+            break;
+        };
+        remaining -= cost;
+        path.push(next.clone());
+        node = next;
+    }
+
+    Ok(Some((best, path)))
+}
+
+impl<N, E> Graph<N, E> {
+    /// Counts the distinct paths between two nodes of a directed acyclic graph.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<u64> {
+        count_paths(from, |&n| self.neighbors(n).map(|(i, _)| i), |&n| n == to)
+    }
+
+    /// Longest path between two nodes of a directed acyclic graph, with edge lengths given by `length`.
+    pub fn longest_path<F>(
+        &self,
+        from: usize,
+        to: usize,
+        length: F,
+    ) -> Result<Option<(u64, Vec<usize>)>>
+    where
+        F: Fn(&E) -> u64,
+    {
+        longest_path_dag(
+            from,
+            |&n| self.neighbors(n).map(|(i, e)| (i, length(e))),
+            |&n| n == to,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_paths_graph() -> Result<()> {
+        let mut graph = Graph::directed();
+        for (from, to) in [
+            ("you", "bbb"),
+            ("you", "ccc"),
+            ("bbb", "ddd"),
+            ("bbb", "eee"),
+            ("ccc", "ddd"),
+            ("ccc", "eee"),
+            ("ccc", "fff"),
+            ("ddd", "ggg"),
+            ("eee", "out"),
+            ("fff", "out"),
+            ("ggg", "out"),
+        ] {
+            graph.add_edge(from, to, ());
+        }
+
+        let you = graph.index(&"you").unwrap();
+        let out = graph.index(&"out").unwrap();
+        assert_eq!(graph.count_paths(you, out)?, 5);
+        assert_eq!(graph.count_paths(out, you)?, 0);
+        assert_eq!(graph.count_paths(you, you)?, 1);
+
+        graph.add_edge("ggg", "you", ());
+        assert!(graph.count_paths(you, out).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_paths_designs() -> Result<()> {
+        // Ways to build a design from towel patterns
+        let towels = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        let count = |design: &str| {
+            count_paths(
+                0usize,
+                |&i| {
+                    towels
+                        .iter()
+                        .filter(|t| design[i..].starts_with(*t))
+                        .map(|t| i + t.len())
+                        .collect::<Vec<_>>()
+                },
+                |&i| i == design.len(),
+            )
+        };
+
+        assert_eq!(count("brwrr")?, 2);
+        assert_eq!(count("gbbr")?, 4);
+        assert_eq!(count("rrbgbr")?, 6);
+        assert_eq!(count("ubwu")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_path() -> Result<()> {
+        let mut graph = Graph::directed();
+        for (from, to, length) in [
+            ('a', 'b', 3),
+            ('a', 'c', 2),
+            ('b', 'd', 4),
+            ('c', 'd', 1),
+            ('c', 'e', 9),
+            ('d', 'e', 1),
+            ('e', 'f', 2),
+            ('z', 'f', 100),
+        ] {
+            graph.add_edge(from, to, length);
+        }
+
+        let index = |n| graph.index(&n).unwrap();
+        let (length, path) = graph.longest_path(index('a'), index('f'), |&l| l)?.unwrap();
+        assert_eq!(length, 13);
+        assert_eq!(
+            path.into_iter().map(|i| *graph.node(i)).collect::<String>(),
+            "acef"
+        );
+
+        assert_eq!(graph.longest_path(index('f'), index('a'), |&l| l)?, None);
+        assert_eq!(
+            graph.longest_path(index('a'), index('a'), |&l| l)?,
+            Some((0, vec![index('a')]))
+        );
+
+        Ok(())
+    }
+}
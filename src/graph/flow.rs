@@ -0,0 +1,294 @@
+use super::Graph;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// A partition of a graph's nodes, given as the node indices on one side, along with the total weight of the edges
+/// crossing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub weight: u64,
+    pub side: Vec<usize>,
+}
+
+impl Cut {
+    /// Sizes of the two sides of the cut, given the graph's node count.
+    pub fn sizes(&self, node_count: usize) -> (usize, usize) {
+        (self.side.len(), node_count - self.side.len())
+    }
+}
+
+struct FlowEdge {
+    to: usize,
+    capacity: u64,
+}
+
+/// Residual network for Dinic's algorithm. Edge `i ^ 1` is the reverse of edge `i`.
+struct Network {
+    edges: Vec<FlowEdge>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Network {
+    fn add_edge(&mut self, from: usize, to: usize, capacity: u64, reverse_capacity: u64) {
+        self.adjacency[from].push(self.edges.len());
+        self.edges.push(FlowEdge { to, capacity });
+        self.adjacency[to].push(self.edges.len());
+        self.edges.push(FlowEdge {
+            to: from,
+            capacity: reverse_capacity,
+        });
+    }
+
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.adjacency.len()];
+        levels[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &e in &self.adjacency[node] {
+                let edge = &self.edges[e];
+                if edge.capacity > 0 && levels[edge.to].is_none() {
+                    levels[edge.to] = levels[node].map(|l| l + 1);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        levels
+    }
+
+    /// Sends a blocking flow along level-increasing paths, iteratively to avoid deep recursion.
+    fn blocking_flow(&mut self, source: usize, sink: usize, levels: &[Option<usize>]) -> u64 {
+        let mut next_edge = vec![0; self.adjacency.len()];
+        let mut total = 0;
+
+        loop {
+            let mut path = Vec::new();
+            let mut node = source;
+            while node != sink {
+                let mut advanced = false;
+                while next_edge[node] < self.adjacency[node].len() {
+                    let e = self.adjacency[node][next_edge[node]];
+                    let edge = &self.edges[e];
+                    if edge.capacity > 0 && levels[edge.to] == levels[node].map(|l| l + 1) {
+                        path.push(e);
+                        node = edge.to;
+                        advanced = true;
+                        break;
+                    }
+                    next_edge[node] += 1;
+                }
+
+                if !advanced {
+                    if node == source {
+                        return total;
+                    }
+                    // Dead end: retreat and never try this edge again
+                    let e = path.pop().unwrap_or_default();
+                    node = self.edges[e ^ 1].to;
+                    next_edge[node] += 1;
+                }
+            }
+
+            let bottleneck = path
+                .iter()
+                .map(|&e| self.edges[e].capacity)
+                .min()
+                .unwrap_or(0);
+            for &e in &path {
+                self.edges[e].capacity -= bottleneck;
+                self.edges[e ^ 1].capacity += bottleneck;
+            }
+            total += bottleneck;
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Maximum flow from `source` to `sink` using Dinic's algorithm, with edge capacities given by `capacity`.
+    /// Undirected edges carry flow in either direction. Returns the flow value as a minimum `source`/`sink` cut, whose
+    /// side lists the nodes still reachable from `source` in the residual network.
+    pub fn max_flow<F>(&self, source: usize, sink: usize, capacity: F) -> Cut
+    where
+        F: Fn(&E) -> u64,
+    {
+        let mut network = Network {
+            edges: Vec::new(),
+            adjacency: vec![Vec::new(); self.len()],
+        };
+        for (from, to, e) in self.edges() {
+            let c = capacity(e);
+            network.add_edge(from, to, c, if self.directed { 0 } else { c });
+        }
+
+        let mut weight = 0;
+        if source != sink {
+            loop {
+                let levels = network.levels(source);
+                if levels[sink].is_none() {
+                    break;
+                }
+                weight += network.blocking_flow(source, sink, &levels);
+            }
+        }
+
+        let side = network
+            .levels(source)
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| l.map(|_| i))
+            .collect();
+
+        Cut { weight, side }
+    }
+
+    /// Global minimum cut of an undirected graph using the Stoer–Wagner algorithm, with edge weights given by `weight`.
+    /// Returns `None` for graphs with fewer than two nodes.
+    pub fn min_cut<F>(&self, weight: F) -> Option<Cut>
+    where
+        F: Fn(&E) -> u64,
+    {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let mut adjacency = vec![HashMap::<usize, u64>::new(); self.len()];
+        for (from, to, e) in self.edges() {
+            if from != to {
+                *adjacency[from].entry(to).or_default() += weight(e);
+                *adjacency[to].entry(from).or_default() += weight(e);
+            }
+        }
+
+        let mut members = (0..self.len()).map(|i| vec![i]).collect::<Vec<_>>();
+        let mut active = (0..self.len()).collect::<Vec<_>>();
+        let mut best: Option<Cut> = None;
+
+        while active.len() > 1 {
+            // Maximum adjacency ordering over the remaining (merged) nodes
+            let mut connectivity = vec![0u64; self.len()];
+            let mut added = vec![false; self.len()];
+            let mut heap = active.iter().map(|&n| (0, n)).collect::<BinaryHeap<_>>();
+            let mut order = Vec::with_capacity(active.len());
+
+            while let Some((w, node)) = heap.pop() {
+                if added[node] || w != connectivity[node] {
+                    continue;
+                }
+                added[node] = true;
+                order.push(node);
+                for (&next, &edge) in &adjacency[node] {
+                    if !added[next] {
+                        connectivity[next] += edge;
+                        heap.push((connectivity[next], next));
+                    }
+                }
+            }
+
+            let t = order[order.len() - 1];
+            let s = order[order.len() - 2];
+
+            if best.as_ref().is_none_or(|b| connectivity[t] < b.weight) {
+                best = Some(Cut {
+                    weight: connectivity[t],
+                    side: members[t].clone(),
+                });
+            }
+
+            // Merge t into s
+            let t_edges = std::mem::take(&mut adjacency[t]);
+            for (next, edge) in t_edges {
+                adjacency[next].remove(&t);
+                if next != s {
+                    *adjacency[s].entry(next).or_default() += edge;
+                    *adjacency[next].entry(s).or_default() += edge;
+                }
+            }
+            let t_members = std::mem::take(&mut members[t]);
+            members[s].extend(t_members);
+            active.retain(|&n| n != t);
+        }
+
+        best.map(|mut cut| {
+            cut.side.sort_unstable();
+            cut
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snowverload() -> Graph<&'static str> {
+        let input = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+        input
+            .lines()
+            .flat_map(|line| {
+                let (from, to) = line.split_once(": ").unwrap();
+                to.split(' ').map(move |to| (from, to))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let graph = snowverload();
+        let cut = graph.min_cut(|_| 1).unwrap();
+        assert_eq!(cut.weight, 3);
+
+        let (a, b) = cut.sizes(graph.len());
+        assert_eq!(a * b, 54);
+
+        assert!(Graph::<u8>::undirected().min_cut(|_| 1).is_none());
+    }
+
+    #[test]
+    fn test_max_flow_undirected() {
+        let graph = snowverload();
+        let source = graph.index(&"jqt").unwrap();
+        let sink = graph.index(&"rsh").unwrap();
+
+        let cut = graph.max_flow(source, sink, |_| 1);
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.side.len() * (graph.len() - cut.side.len()), 54);
+        assert!(cut.side.contains(&source));
+        assert!(!cut.side.contains(&sink));
+    }
+
+    #[test]
+    fn test_max_flow_directed() {
+        let mut graph = Graph::directed();
+        for (from, to, capacity) in [
+            ('s', 'a', 10),
+            ('s', 'c', 10),
+            ('a', 'b', 4),
+            ('a', 'c', 2),
+            ('a', 'd', 8),
+            ('c', 'd', 9),
+            ('d', 'b', 6),
+            ('b', 't', 10),
+            ('d', 't', 10),
+        ] {
+            graph.add_edge(from, to, capacity);
+        }
+
+        let s = graph.index(&'s').unwrap();
+        let t = graph.index(&'t').unwrap();
+        assert_eq!(graph.max_flow(s, t, |&c| c).weight, 19);
+
+        // Reversed, nothing can flow
+        assert_eq!(graph.max_flow(t, s, |&c| c).weight, 0);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The rho shape traced from a start node: `tail` steps lead into a cycle of `cycle_len` nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rho {
+    pub tail: usize,
+    pub cycle_len: usize,
+    /// Identifies the cycle, for looking up its members with [`FunctionalGraph::cycle`]
+    pub cycle: usize,
+    /// Index in the cycle of the first cycle node reached
+    pub entry: usize,
+}
+
+/// Analyzes the functional graph of `next`, where every node has exactly one successor. Every walk ends in a cycle,
+/// and each start's shape is memoized for every node on its walk, so queries for many starts that feed into shared
+/// cycles cost time proportional to the nodes not seen before.
+pub struct FunctionalGraph<N, F> {
+    next: F,
+    rhos: HashMap<N, Rho>,
+    cycles: Vec<Vec<N>>,
+}
+
+impl<N, F> FunctionalGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> N,
+{
+    pub fn new(next: F) -> Self {
+        Self {
+            next,
+            rhos: HashMap::new(),
+            cycles: Vec::new(),
+        }
+    }
+
+    /// The tail length and cycle reached from `start`.
+    pub fn rho(&mut self, start: &N) -> Rho {
+        if let Some(&rho) = self.rhos.get(start) {
+            return rho;
+        }
+
+        let mut path: Vec<N> = Vec::new();
+        let mut on_path = HashMap::new();
+        let mut node = start.clone();
+        let reached = loop {
+            if let Some(&rho) = self.rhos.get(&node) {
+                break rho;
+            }
+            if let Some(&i) = on_path.get(&node) {
+                // a new cycle: path[i..]
+                let members = path.split_off(i);
+                let cycle = self.cycles.len();
+                for (entry, member) in members.iter().enumerate() {
+                    let rho = Rho {
+                        tail: 0,
+                        cycle_len: members.len(),
+                        cycle,
+                        entry,
+                    };
+                    self.rhos.insert(member.clone(), rho);
+                }
+                let rho = self.rhos[&members[0]];
+                self.cycles.push(members);
+                break rho;
+            }
+            on_path.insert(node.clone(), path.len());
+            let next = (self.next)(&node);
+            path.push(std::mem::replace(&mut node, next));
+        };
+
+        // what's left of the path is a tail leading into `reached`
+        let len = path.len();
+        for (i, n) in path.into_iter().enumerate() {
+            let rho = Rho {
+                tail: reached.tail + len - i,
+                ..reached
+            };
+            self.rhos.insert(n, rho);
+        }
+        self.rhos[start]
+    }
+
+    /// The members of a cycle, in walk order, by [`Rho::cycle`].
+    pub fn cycle(&self, cycle: usize) -> &[N] {
+        &self.cycles[cycle]
+    }
+
+    /// The cycle reached from `start`, in walk order starting from its entry node.
+    pub fn cycle_from(&mut self, start: &N) -> Vec<N> {
+        let rho = self.rho(start);
+        let members = &self.cycles[rho.cycle];
+        members[rho.entry..]
+            .iter()
+            .chain(&members[..rho.entry])
+            .cloned()
+            .collect()
+    }
+
+    /// The node reached after `steps` steps from `start`, skipping whole laps of the cycle.
+    pub fn nth(&mut self, start: &N, steps: u64) -> N {
+        let rho = self.rho(start);
+        if steps < rho.tail as u64 {
+            let mut node = start.clone();
+            for _ in 0..steps {
+                node = (self.next)(&node);
+            }
+            return node;
+        }
+        let laps = (steps - rho.tail as u64) % rho.cycle_len as u64;
+        let index = (rho.entry + laps as usize) % rho.cycle_len;
+        self.cycles[rho.cycle][index].clone()
+    }
+
+    /// The number of distinct cycles found so far.
+    pub fn cycle_count(&self) -> usize {
+        self.cycles.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rho() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2, and 5 -> 6 -> 6
+        let next = |&n: &u32| match n {
+            4 => 2,
+            6 => 6,
+            n => n + 1,
+        };
+        let mut graph = FunctionalGraph::new(next);
+
+        let rho = graph.rho(&0);
+        assert_eq!((rho.tail, rho.cycle_len), (2, 3));
+        assert_eq!(graph.cycle(rho.cycle), [2, 3, 4]);
+        assert_eq!(graph.rho(&1).tail, 1);
+        assert_eq!(
+            graph.rho(&3),
+            Rho {
+                tail: 0,
+                cycle_len: 3,
+                cycle: 0,
+                entry: 1
+            }
+        );
+        assert_eq!(graph.cycle_from(&3), [3, 4, 2]);
+
+        let rho = graph.rho(&5);
+        assert_eq!((rho.tail, rho.cycle_len, rho.cycle), (1, 1, 1));
+        assert_eq!(graph.cycle_count(), 2);
+
+        for steps in 0..20 {
+            let walked = (0..steps).fold(0, |n, _| next(&n));
+            assert_eq!(graph.nth(&0, steps), walked, "{steps} steps");
+        }
+        assert_eq!(graph.nth(&0, 1_000_000_000_000), 4);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Cut, Graph};
+use crate::math::Rng;
+
+impl<N, E> Graph<N, E> {
+    /// Minimum cut of an undirected graph by Karger's randomized contraction, counting each edge as weight 1.
+    ///
+    /// Each attempt contracts random edges until two groups of nodes remain, which finds a minimum cut with
+    /// probability at least `2 / n²`. Up to `attempts` attempts are made, stopping early once a cut of at most
+    /// `target` edges is found — when the cut size is known, as in "cut three wires", that is usually after a handful.
+    /// Returns the smallest cut seen, or `None` for graphs with fewer than two nodes. The same `rng` seed gives the
+    /// same result.
+    pub fn karger_min_cut(
+        &self,
+        rng: &mut Rng,
+        attempts: usize,
+        target: Option<u64>,
+    ) -> Option<Cut> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let mut edges = self
+            .edges()
+            .filter(|&(from, to, _)| from != to)
+            .map(|(from, to, _)| (from, to))
+            .collect::<Vec<_>>();
+        let mut best: Option<Cut> = None;
+
+        for _ in 0..attempts {
+            // contracting edges in a random order is contracting a random remaining edge each time
+            rng.shuffle(&mut edges);
+            let mut groups = Groups::new(self.len());
+            let mut remaining = self.len();
+            for &(from, to) in &edges {
+                if remaining == 2 {
+                    break;
+                }
+                if groups.union(from, to) {
+                    remaining -= 1;
+                }
+            }
+
+            // a disconnected graph can leave more than two groups; any one of them is a cut of weight 0
+            let side_root = groups.find(0);
+            let weight = edges
+                .iter()
+                .filter(|&&(from, to)| groups.find(from) != groups.find(to))
+                .count() as u64;
+            let weight = if remaining > 2 { 0 } else { weight };
+
+            if best.as_ref().is_none_or(|b| weight < b.weight) {
+                let side = (0..self.len())
+                    .filter(|&i| groups.find(i) == side_root)
+                    .collect();
+                best = Some(Cut { weight, side });
+            }
+            if best
+                .as_ref()
+                .is_some_and(|b| target.is_some_and(|t| b.weight <= t))
+            {
+                break;
+            }
+        }
+        best
+    }
+}
+
+/// Union-find over node indices, with path halving.
+struct Groups {
+    parent: Vec<usize>,
+}
+
+impl Groups {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Joins the groups of `a` and `b`, returning whether they were separate.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parent[a] = b;
+        a != b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_karger_min_cut() {
+        // two 5-cliques joined by three edges
+        let mut graph = Graph::undirected();
+        for side in [0, 10] {
+            for a in side..side + 5 {
+                for b in a + 1..side + 5 {
+                    graph.add_edge(a, b, ());
+                }
+            }
+        }
+        for (a, b) in [(0, 10), (1, 11), (2, 12)] {
+            graph.add_edge(a, b, ());
+        }
+
+        let cut = graph
+            .karger_min_cut(&mut Rng::new(25), 1000, Some(3))
+            .unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.sizes(graph.len()), (5, 5));
+
+        // deterministic for a given seed
+        let again = graph.karger_min_cut(&mut Rng::new(25), 1000, Some(3));
+        assert_eq!(again, Some(cut));
+
+        assert!(
+            Graph::<u8>::undirected()
+                .karger_min_cut(&mut Rng::new(1), 10, None)
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,393 @@
+use anyhow::{Result, bail};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+mod clique;
+mod contract;
+mod dag;
+mod flow;
+mod functional;
+mod karger;
+mod tsp;
+
+pub use dag::{count_paths, longest_path_dag};
+pub use flow::Cut;
+pub use functional::{FunctionalGraph, Rho};
+pub use tsp::{Objective, Route, held_karp};
+
+/// A graph stored as adjacency lists. Node labels of any hashable type are interned to dense indices in insertion
+/// order, so algorithms can use `Vec`-indexed storage.
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    directed: bool,
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    adjacency: Vec<Vec<(usize, E)>>,
+    edge_count: usize,
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            adjacency: Vec::new(),
+            edge_count: 0,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of edges, counting each undirected edge once.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    /// Neighbors of the node at `index`, as `(neighbor index, edge weight)` pairs.
+    pub fn neighbors(&self, index: usize) -> impl Iterator<Item = (usize, &E)> {
+        self.adjacency[index].iter().map(|(i, e)| (*i, e))
+    }
+
+    pub fn degree(&self, index: usize) -> usize {
+        self.adjacency[index].len()
+    }
+
+    /// All edges as `(from, to, weight)` index triples. Undirected edges are listed once, with `from <= to`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &E)> {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(move |(from, adj)| {
+                adj.iter()
+                    .filter(move |(to, _)| self.directed || from <= *to)
+                    .map(move |(to, e)| (from, *to, e))
+            })
+    }
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// Returns the index of `node`, adding it if it isn't already present.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.adjacency.push(Vec::new());
+        index
+    }
+
+    pub fn index(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    /// Neighbors of `node` by label, as `(neighbor, edge weight)` pairs.
+    pub fn neighbors_of(&self, node: &N) -> impl Iterator<Item = (&N, &E)> {
+        self.index(node)
+            .into_iter()
+            .flat_map(|i| self.neighbors(i))
+            .map(|(i, e)| (&self.nodes[i], e))
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.index(from), self.index(to)) {
+            (Some(from), Some(to)) => self.adjacency[from].iter().any(|(i, _)| *i == to),
+            _ => false,
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash, E: Clone> Graph<N, E> {
+    /// Adds an edge, adding either endpoint if needed, and returns the endpoints' indices. Undirected edges are
+    /// stored in both adjacency lists.
+    pub fn add_edge(&mut self, from: N, to: N, weight: E) -> (usize, usize) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        if !self.directed && from != to {
+            self.adjacency[to].push((from, weight.clone()));
+        }
+        self.adjacency[from].push((to, weight));
+        self.edge_count += 1;
+        (from, to)
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Graph<N> {
+    /// Builds an undirected, unweighted graph from pairs of nodes.
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        let mut graph = Self::undirected();
+        for (from, to) in iter {
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+}
+
+/// Orders `nodes` so each appears after all of its `dependencies`. Dependencies that aren't among `nodes` are ignored.
+/// Nodes that are free to go next are taken in the order they were given, so the result is deterministic.
+///
+/// Fails if the dependencies contain a cycle.
+pub fn topological_sort<N, I, F>(
+    nodes: impl IntoIterator<Item = N>,
+    dependencies: F,
+) -> Result<Vec<N>>
+where
+    N: Clone + Eq + Hash + Debug,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    sort_indexed(nodes.into_iter().collect(), dependencies)
+}
+
+/// Like [`topological_sort`], but nodes that are free to go next are taken in lexicographic order.
+pub fn topological_sort_lexicographic<N, I, F>(
+    nodes: impl IntoIterator<Item = N>,
+    dependencies: F,
+) -> Result<Vec<N>>
+where
+    N: Clone + Ord + Hash + Debug,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+    nodes.sort();
+    nodes.dedup();
+    sort_indexed(nodes, dependencies)
+}
+
+fn sort_indexed<N, I, F>(nodes: Vec<N>, mut dependencies: F) -> Result<Vec<N>>
+where
+    N: Clone + Eq + Hash + Debug,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut indices = HashMap::new();
+    let mut unique = Vec::new();
+    for node in nodes {
+        if !indices.contains_key(&node) {
+            indices.insert(node.clone(), unique.len());
+            unique.push(node);
+        }
+    }
+
+    let mut dependents = vec![Vec::new(); unique.len()];
+    let mut pending = vec![0usize; unique.len()];
+    for (i, node) in unique.iter().enumerate() {
+        for dependency in dependencies(node) {
+            if let Some(&d) = indices.get(&dependency) {
+                dependents[d].push(i);
+                pending[i] += 1;
+            }
+        }
+    }
+
+    let mut ready = (0..unique.len())
+        .filter(|&i| pending[i] == 0)
+        .collect::<BTreeSet<_>>();
+    let mut order = Vec::with_capacity(unique.len());
+
+    while let Some(i) = ready.pop_first() {
+        order.push(i);
+        for &dependent in &dependents[i] {
+            pending[dependent] -= 1;
+            if pending[dependent] == 0 {
+                ready.insert(dependent);
+            }
+        }
+    }
+
+    if order.len() < unique.len() {
+        let cyclic = (0..unique.len())
+            .filter(|&i| pending[i] > 0)
+            .map(|i| &unique[i])
+            .collect::<Vec<_>>();
+        bail!("Dependency cycle among {:?}", cyclic);
+    }
+
+    Ok(order.into_iter().map(|i| unique[i].clone()).collect())
+}
+
+/// Whether every node in `sequence` appears after all of its `dependencies` that are also in `sequence`.
+pub fn is_topologically_ordered<N, I, F>(sequence: &[N], mut dependencies: F) -> bool
+where
+    N: Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let positions = sequence
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n, i))
+        .collect::<HashMap<_, _>>();
+
+    sequence.iter().enumerate().all(|(i, node)| {
+        dependencies(node)
+            .into_iter()
+            .all(|d| positions.get(&d).is_none_or(|&p| p < i))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_undirected() {
+        let graph = [("kh", "tc"), ("qp", "kh"), ("de", "cg"), ("kh", "ta")]
+            .into_iter()
+            .collect::<Graph<_>>();
+
+        assert!(!graph.is_directed());
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.nodes(), &["kh", "tc", "qp", "de", "cg", "ta"]);
+
+        let kh = graph.index(&"kh").unwrap();
+        assert_eq!(graph.node(kh), &"kh");
+        assert_eq!(graph.degree(kh), 3);
+        assert!(graph.has_edge(&"tc", &"kh"));
+        assert!(!graph.has_edge(&"tc", &"qp"));
+        assert!(!graph.has_edge(&"tc", &"zz"));
+
+        let mut neighbors = graph
+            .neighbors_of(&"kh")
+            .map(|(n, _)| *n)
+            .collect::<Vec<_>>();
+        neighbors.sort();
+        assert_eq!(neighbors, vec!["qp", "ta", "tc"]);
+        assert_eq!(graph.neighbors_of(&"zz").count(), 0);
+        assert_eq!(graph.edges().count(), 4);
+    }
+
+    #[test]
+    fn test_graph_directed() {
+        let mut graph = Graph::directed();
+        assert!(graph.is_empty());
+
+        assert_eq!(graph.add_edge("a", "b", 5), (0, 1));
+        assert_eq!(graph.add_edge("b", "c", 7), (1, 2));
+        assert_eq!(graph.add_edge("a", "c", 1), (0, 2));
+        assert_eq!(graph.add_node("c"), 2);
+        assert_eq!(graph.add_node("d"), 3);
+
+        assert!(graph.is_directed());
+        assert!(graph.has_edge(&"a", &"b"));
+        assert!(!graph.has_edge(&"b", &"a"));
+        assert_eq!(
+            graph.neighbors(0).collect::<Vec<_>>(),
+            vec![(1, &5), (2, &1)]
+        );
+        assert_eq!(graph.degree(3), 0);
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(0, 1, &5), (0, 2, &1), (1, 2, &7)]
+        );
+    }
+
+    fn steps() -> HashMap<char, Vec<char>> {
+        // Step X must be finished before step Y can begin
+        let mut deps: HashMap<char, Vec<char>> = HashMap::new();
+        for (before, after) in [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ] {
+            deps.entry(after).or_default().push(before);
+        }
+        deps
+    }
+
+    #[test]
+    fn test_topological_sort_lexicographic() -> Result<()> {
+        let deps = steps();
+        let order = topological_sort_lexicographic("FEDCBA".chars(), |n| {
+            deps.get(n).cloned().unwrap_or_default()
+        })?;
+        assert_eq!(order.into_iter().collect::<String>(), "CABDFE");
+        Ok(())
+    }
+
+    #[test]
+    fn test_topological_sort_insertion_order() -> Result<()> {
+        let deps = steps();
+        let order = topological_sort("FEDCBA".chars(), |n| {
+            deps.get(n).cloned().unwrap_or_default()
+        })?;
+        assert_eq!(order.into_iter().collect::<String>(), "CFADBE");
+        assert!(is_topologically_ordered(
+            &"CFADBE".chars().collect::<Vec<_>>(),
+            |n| { deps.get(n).cloned().unwrap_or_default() }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let deps = HashMap::from([(1, vec![3]), (2, vec![1]), (3, vec![2]), (4, vec![])]);
+        let err = topological_sort([1, 2, 3, 4], |n| deps[n].clone()).unwrap_err();
+        assert_eq!(err.to_string(), "Dependency cycle among [1, 2, 3]");
+    }
+
+    #[test]
+    fn test_is_topologically_ordered() {
+        // Page ordering rules: X|Y means X must be printed before Y
+        let rules = [
+            (47, 53),
+            (97, 13),
+            (97, 61),
+            (75, 29),
+            (61, 13),
+            (29, 13),
+            (97, 47),
+        ];
+        let before = |page: &u32| {
+            rules
+                .iter()
+                .filter(|(_, after)| after == page)
+                .map(|(before, _)| *before)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(is_topologically_ordered(&[75, 47, 61, 53, 29], before));
+        assert!(!is_topologically_ordered(&[61, 13, 29, 97], before));
+        assert!(is_topologically_ordered(&[], before));
+
+        let fixed = topological_sort([61, 13, 29, 97], before).unwrap();
+        assert_eq!(fixed, vec![29, 97, 61, 13]);
+        assert!(is_topologically_ordered(&fixed, before));
+    }
+}
@@ -0,0 +1,163 @@
+/// Whether [`held_karp`] looks for the cheapest or the most expensive route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    Shortest,
+    Longest,
+}
+
+/// The shape of route [`held_karp`] looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Visit every node exactly once, ending anywhere
+    Path,
+    /// Visit every node exactly once, then return to the start
+    Cycle,
+}
+
+impl Objective {
+    fn better(self, a: u64, b: u64) -> bool {
+        match self {
+            Objective::Shortest => a < b,
+            Objective::Longest => a > b,
+        }
+    }
+}
+
+/// Solves the traveling salesman problem over a square `distances` matrix with Held–Karp bitmask dynamic
+/// programming, which is practical for up to roughly 20 nodes. Routes start at `start` if given; otherwise paths may
+/// start anywhere and cycles start at node 0.
+///
+/// Returns the route's total distance and the order the nodes are visited in. A cycle's return to its start is
+/// included in the distance but not repeated in the order, and `None` if there are no nodes or `start` isn't one of
+/// them.
+pub fn held_karp(
+    distances: &[Vec<u64>],
+    objective: Objective,
+    route: Route,
+    start: Option<usize>,
+) -> Option<(u64, Vec<usize>)> {
+    let n = distances.len();
+    if n == 0 || start.is_some_and(|s| s >= n) {
+        return None;
+    }
+    let start = match (route, start) {
+        (Route::Cycle, None) => Some(0),
+        (_, start) => start,
+    };
+
+    let full = (1usize << n) - 1;
+    let index = |mask: usize, last: usize| mask * n + last;
+    let mut best: Vec<Option<u64>> = vec![None; (1 << n) * n];
+    let mut parent = vec![usize::MAX; (1 << n) * n];
+
+    match start {
+        Some(s) => best[index(1 << s, s)] = Some(0),
+        None => (0..n).for_each(|i| best[index(1 << i, i)] = Some(0)),
+    }
+
+    for mask in 1..=full {
+        for last in (0..n).filter(|&l| mask & (1 << l) != 0) {
+            let Some(cost) = best[index(mask, last)] else {
+                continue;
+            };
+            for next in (0..n).filter(|&m| mask & (1 << m) == 0) {
+                let next_mask = mask | (1 << next);
+                let next_cost = cost + distances[last][next];
+                let slot = &mut best[index(next_mask, next)];
+                if slot.is_none_or(|c| objective.better(next_cost, c)) {
+                    *slot = Some(next_cost);
+                    parent[index(next_mask, next)] = last;
+                }
+            }
+        }
+    }
+
+    let (total, mut last) = (0..n)
+        .filter_map(|last| {
+            let cost = best[index(full, last)]?;
+            let back = match (route, start) {
+                (Route::Cycle, Some(s)) => distances[last][s],
+                _ => 0,
+            };
+            Some((cost + back, last))
+        })
+        .reduce(|a, b| if objective.better(b.0, a.0) { b } else { a })?;
+
+    let mut order = Vec::with_capacity(n);
+    let mut mask = full;
+    loop {
+        order.push(last);
+        let prev = parent[index(mask, last)];
+        if prev == usize::MAX {
+            break;
+        }
+        mask &= !(1 << last);
+        last = prev;
+    }
+    order.reverse();
+
+    Some((total, order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cities() -> Vec<Vec<u64>> {
+        // London, Dublin, Belfast
+        vec![vec![0, 464, 518], vec![464, 0, 141], vec![518, 141, 0]]
+    }
+
+    #[test]
+    fn test_held_karp_paths() {
+        let (shortest, order) =
+            held_karp(&cities(), Objective::Shortest, Route::Path, None).unwrap();
+        assert_eq!(shortest, 605);
+        assert!(order == vec![0, 1, 2] || order == vec![2, 1, 0]);
+
+        let (longest, order) = held_karp(&cities(), Objective::Longest, Route::Path, None).unwrap();
+        assert_eq!(longest, 982);
+        assert!(order == vec![1, 0, 2] || order == vec![2, 0, 1]);
+
+        let (from_dublin, order) =
+            held_karp(&cities(), Objective::Shortest, Route::Path, Some(1)).unwrap();
+        assert_eq!(from_dublin, 659);
+        assert_eq!(order, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_held_karp_cycle() {
+        let distances = vec![
+            vec![0, 2, 9, 10],
+            vec![1, 0, 6, 4],
+            vec![15, 7, 0, 8],
+            vec![6, 3, 12, 0],
+        ];
+
+        let (cost, order) = held_karp(&distances, Objective::Shortest, Route::Cycle, None).unwrap();
+        assert_eq!(cost, 21);
+        assert_eq!(order, vec![0, 2, 3, 1]);
+
+        let (cost, order) =
+            held_karp(&distances, Objective::Shortest, Route::Cycle, Some(2)).unwrap();
+        assert_eq!(cost, 21);
+        assert_eq!(order, vec![2, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_held_karp_trivial() {
+        assert_eq!(held_karp(&[], Objective::Shortest, Route::Path, None), None);
+        assert_eq!(
+            held_karp(&[vec![0]], Objective::Longest, Route::Cycle, None),
+            Some((0, vec![0]))
+        );
+        assert_eq!(
+            held_karp(&cities(), Objective::Shortest, Route::Path, Some(3)),
+            None
+        );
+        assert_eq!(
+            held_karp(&cities(), Objective::Shortest, Route::Cycle, Some(7)),
+            None
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Direction, Grid};
+use crate::math::two_dimensional::Point;
+use proptest::prelude::*;
+use proptest::sample::select;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// The side lengths `any::<Grid<T>>()` draws from; use [`grids`] for other sizes.
+pub const DEFAULT_SIDES: RangeInclusive<usize> = 1..=12;
+
+/// Rectangular grids with cells drawn from `cell`. Shrinks toward fewer rows and columns, then simpler cells.
+pub fn grids<S>(
+    cell: S,
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+) -> impl Strategy<Value = Grid<S::Value>>
+where
+    S: Strategy + Clone,
+{
+    (width, height).prop_flat_map(move |(width, height)| {
+        proptest::collection::vec(proptest::collection::vec(cell.clone(), width), height)
+            .prop_map(Grid)
+    })
+}
+
+/// A grid from [`grids`] together with a point inside it.
+pub fn grids_with_point<S>(
+    cell: S,
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Grid<S::Value>, Point)>
+where
+    S: Strategy + Clone,
+    S::Value: Clone,
+{
+    grids(cell, width, height).prop_flat_map(|grid| {
+        let point = (0..grid.width(), 0..grid.height()).prop_map(|(x, y)| Point::new(x, y));
+        (Just(grid), point)
+    })
+}
+
+impl<T> Arbitrary for Grid<T>
+where
+    T: Arbitrary + Clone + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        grids(Arc::new(any_with::<T>(args)), DEFAULT_SIDES, DEFAULT_SIDES).boxed()
+    }
+}
+
+impl Arbitrary for Direction {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(&Direction::ALL[..]).boxed()
+    }
+}
+
+impl<T> Arbitrary for Point<T>
+where
+    T: Arbitrary + Display + 'static,
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (any_with::<T>(args.clone()), any_with::<T>(args))
+            .prop_map(|(x, y)| Point::new(x, y))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{neighbor_in_direction, neighbors};
+
+    proptest! {
+        #[test]
+        fn test_grids_are_rectangular(grid in any::<Grid<u8>>()) {
+            prop_assert!(DEFAULT_SIDES.contains(&grid.height()));
+            prop_assert!(grid.iter().all(|row| row.len() == grid.width()));
+        }
+
+        #[test]
+        fn test_neighbors_in_bounds(
+            (grid, point) in grids_with_point(any::<bool>(), 1..=8, 1..=8),
+            diagonals in any::<bool>(),
+        ) {
+            for neighbor in neighbors(&grid, point, diagonals) {
+                prop_assert!(grid.get(neighbor.position).is_some());
+                let back = neighbor_in_direction(&grid, neighbor.direction.opposite(), neighbor.position);
+                prop_assert_eq!(back.map(|n| n.position), Some(point));
+            }
+        }
+
+        #[test]
+        fn test_opposite_is_involution(direction in any::<Direction>()) {
+            prop_assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+}
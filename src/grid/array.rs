@@ -0,0 +1,170 @@
+use super::{Direction, Grid, Neighbor, neighbor_directions};
+use crate::error::{Error, Result};
+use crate::math::two_dimensional::Point;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+
+/// A `W`x`H` grid stored inline in nested arrays, for boards whose size is known up front (keypads, bingo cards). Same
+/// `Point` API as [`Grid`], without the heap allocation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ArrayGrid<T, const W: usize, const H: usize>([[T; W]; H]);
+
+impl<T, const W: usize, const H: usize> ArrayGrid<T, W, H> {
+    pub fn new(rows: [[T; W]; H]) -> Self {
+        Self(rows)
+    }
+
+    pub fn filled(value: T) -> Self
+    where
+        T: Copy,
+    {
+        Self([[value; W]; H])
+    }
+
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.0.get(position.y)?.get(position.x)
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.0.get_mut(position.y)?.get_mut(position.x)
+    }
+
+    /// Every position, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        (0..H).flat_map(|y| (0..W).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn neighbor_in_direction(&self, direction: Direction, position: Point) -> Option<Neighbor> {
+        let (dx, dy) = direction.delta();
+        let x = position.x.checked_add_signed(dx)?;
+        let y = position.y.checked_add_signed(dy)?;
+        (x < W && y < H).then(|| Neighbor::new(direction, Point::new(x, y)))
+    }
+
+    /// Like [`super::neighbors`], without collecting into a `Vec`.
+    pub fn neighbors(
+        &self,
+        position: Point,
+        include_diagonals: bool,
+    ) -> impl Iterator<Item = Neighbor> + '_ {
+        neighbor_directions(include_diagonals)
+            .iter()
+            .filter_map(move |&d| self.neighbor_in_direction(d, position))
+    }
+}
+
+impl<T: Default + Copy, const W: usize, const H: usize> Default for ArrayGrid<T, W, H> {
+    fn default() -> Self {
+        Self::filled(T::default())
+    }
+}
+
+impl<T, const W: usize, const H: usize> Deref for ArrayGrid<T, W, H> {
+    type Target = [[T; W]; H];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const W: usize, const H: usize> DerefMut for ArrayGrid<T, W, H> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<Point> for ArrayGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        &self.0[point.y][point.x]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<Point> for ArrayGrid<T, W, H> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        &mut self.0[point.y][point.x]
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> TryFrom<&Grid<T>> for ArrayGrid<T, W, H> {
+    type Error = Error;
+
+    fn try_from(grid: &Grid<T>) -> Result<Self> {
+        if (grid.width(), grid.height()) != (W, H) {
+            return Err(Error::GridSize {
+                expected: (W, H),
+                found: (grid.width(), grid.height()),
+            });
+        }
+        Ok(Self(core::array::from_fn(|y| {
+            core::array::from_fn(|x| grid.0[y][x].clone())
+        })))
+    }
+}
+
+impl<T, const W: usize, const H: usize> From<ArrayGrid<T, W, H>> for Grid<T> {
+    fn from(grid: ArrayGrid<T, W, H>) -> Self {
+        Grid(grid.0.into_iter().map(Vec::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::neighbors;
+    use alloc::vec;
+    use anyhow::Result;
+
+    #[test]
+    fn test_array_grid() {
+        let mut keypad = ArrayGrid::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!((keypad.width(), keypad.height()), (3, 3));
+        assert_eq!(keypad[Point::new(2, 0)], 3);
+        assert_eq!(keypad.get(Point::new(3, 0)), None);
+
+        keypad[Point::new(1, 1)] = 0;
+        assert_eq!(keypad.0[1], [4, 0, 6]);
+        assert_eq!(keypad.points().count(), 9);
+        assert_eq!(keypad.points().last(), Some(Point::new(2, 2)));
+    }
+
+    #[test]
+    fn test_array_grid_neighbors_match_grid() {
+        let board = ArrayGrid::<u8, 5, 4>::default();
+        let grid = Grid::from(board);
+        for point in board.points() {
+            for diagonals in [false, true] {
+                assert_eq!(
+                    board.neighbors(point, diagonals).collect::<Vec<_>>(),
+                    neighbors(&grid, point, diagonals)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_array_grid_from_grid() -> Result<()> {
+        let grid = Grid::try_from(vec![vec!['a', 'b'], vec!['c', 'd']])?;
+        let board = ArrayGrid::<char, 2, 2>::try_from(&grid)?;
+        assert_eq!(board[Point::new(0, 1)], 'c');
+
+        let err = ArrayGrid::<char, 3, 2>::try_from(&grid).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::GridSize {
+                expected: (3, 2),
+                found: (2, 2)
+            }
+        ));
+        Ok(())
+    }
+}
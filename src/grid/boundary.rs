@@ -0,0 +1,188 @@
+use super::{Cardinal, Grid};
+use crate::math::two_dimensional::{IPoint, Point};
+use std::collections::{BTreeMap, HashSet};
+
+/// One closed loop of a region's outline, as the lattice corners where it turns. Cell `(x, y)` spans the corners
+/// `(x, y)` to `(x + 1, y + 1)`, and the loop runs with the region on its right: clockwise on screen around the
+/// outside of a region, counterclockwise around a hole in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outline {
+    corners: Vec<IPoint>,
+}
+
+impl Outline {
+    /// The corners in walking order, starting from the loop's top-left-most corner.
+    pub fn corners(&self) -> &[IPoint] {
+        &self.corners
+    }
+
+    /// The number of straight sides, which is also the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners.len()
+    }
+
+    /// The total length of the loop's unit edges.
+    pub fn perimeter(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| a.manhattan_distance(&b) as u64)
+            .sum()
+    }
+
+    /// The enclosed area by the shoelace formula: positive for an outer loop and negative for a hole, so the areas of
+    /// all a region's loops sum to its number of cells.
+    pub fn area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum::<i64>()
+            / 2
+    }
+
+    pub fn is_hole(&self) -> bool {
+        self.area() < 0
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (IPoint, IPoint)> + '_ {
+        self.corners
+            .iter()
+            .copied()
+            .zip(self.corners.iter().copied().cycle().skip(1))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Traces the outline of `region`, a set of cells in this grid, as closed loops: one around the outside of each
+    /// 4-connected piece, and one around each hole. Cells that only touch diagonally get separate loops. Cells
+    /// outside the grid are ignored.
+    pub fn trace_boundary(&self, region: &HashSet<Point>) -> Vec<Outline> {
+        let inside = |x: i64, y: i64| {
+            x >= 0 && y >= 0 && {
+                let cell = Point::new(x as usize, y as usize);
+                self.get(cell).is_some() && region.contains(&cell)
+            }
+        };
+
+        // every unit edge between a region cell and a non-region cell, directed to keep the region on its right
+        let mut edges: BTreeMap<(i64, i64), Vec<Cardinal>> = BTreeMap::new();
+        for cell in region.iter().filter(|&&cell| self.get(cell).is_some()) {
+            let (x, y) = (cell.x as i64, cell.y as i64);
+            for (heading, neighbor, start) in [
+                (Cardinal::Right, (x, y - 1), (x, y)),
+                (Cardinal::Down, (x + 1, y), (x + 1, y)),
+                (Cardinal::Left, (x, y + 1), (x + 1, y + 1)),
+                (Cardinal::Up, (x - 1, y), (x, y + 1)),
+            ] {
+                if !inside(neighbor.0, neighbor.1) {
+                    // keyed (y, x) so the first key is the top-left-most corner
+                    edges.entry((start.1, start.0)).or_default().push(heading);
+                }
+            }
+        }
+
+        let mut outlines = Vec::new();
+        // corners are removed once all their edges are walked, so the first key always starts a new loop
+        while let Some((&start, out)) = edges.first_key_value() {
+            let mut position = start;
+            let mut heading = out[0];
+            let mut steps = Vec::new();
+            loop {
+                let out = edges
+                    .get_mut(&position)
+                    .expect("boundary edges form closed loops");
+                // hugging the region by turning right first keeps diagonal neighbors on separate loops
+                let index = [heading.turn_right(), heading, heading.turn_left()]
+                    .into_iter()
+                    .find_map(|h| out.iter().position(|&o| o == h))
+                    .expect("boundary edges form closed loops");
+                heading = out.swap_remove(index);
+                if out.is_empty() {
+                    edges.remove(&position);
+                }
+                steps.push((position, heading));
+
+                let (dx, dy) = heading.delta();
+                position = (position.0 + dy as i64, position.1 + dx as i64);
+                if position == start {
+                    break;
+                }
+            }
+
+            let corners = (0..steps.len())
+                .filter(|&i| steps[i].1 != steps[(i + steps.len() - 1) % steps.len()].1)
+                .map(|i| IPoint::new(steps[i].0.1, steps[i].0.0))
+                .collect();
+            outlines.push(Outline { corners });
+        }
+        outlines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+    use anyhow::Result;
+
+    fn region(grid: &Grid<char>, c: char) -> HashSet<Point> {
+        grid.iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &v)| (x, y, v)))
+            .filter(|&(_, _, v)| v == c)
+            .map(|(x, y, _)| Point::new(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_trace_boundary_square() -> Result<()> {
+        let grid = Grid::from_lines(&["AA", "AA"], YAxis::Down, |c| c)?;
+        let outlines = grid.trace_boundary(&region(&grid, 'A'));
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(
+            outlines[0].corners(),
+            [
+                IPoint::new(0, 0),
+                IPoint::new(2, 0),
+                IPoint::new(2, 2),
+                IPoint::new(0, 2)
+            ]
+        );
+        assert_eq!(outlines[0].sides(), 4);
+        assert_eq!(outlines[0].perimeter(), 8);
+        assert_eq!(outlines[0].area(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_boundary_hole_and_concave() -> Result<()> {
+        let grid = Grid::from_lines(
+            &["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"],
+            YAxis::Down,
+            |c| c,
+        )?;
+        let outlines = grid.trace_boundary(&region(&grid, 'E'));
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].sides(), 12);
+        assert_eq!(outlines[0].perimeter(), 36);
+        assert_eq!(outlines[0].area(), 17);
+
+        let grid = Grid::from_lines(
+            &["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"],
+            YAxis::Down,
+            |c| c,
+        )?;
+        let outlines = grid.trace_boundary(&region(&grid, 'O'));
+        assert_eq!(outlines.len(), 5);
+        assert_eq!(outlines.iter().filter(|o| o.is_hole()).count(), 4);
+        assert_eq!(outlines.iter().map(Outline::sides).sum::<usize>(), 20);
+        assert_eq!(outlines.iter().map(Outline::area).sum::<i64>(), 21);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_boundary_diagonal_cells() -> Result<()> {
+        let grid = Grid::from_lines(&["AB", "BA"], YAxis::Down, |c| c)?;
+        let outlines = grid.trace_boundary(&region(&grid, 'A'));
+        assert_eq!(outlines.len(), 2);
+        assert!(outlines.iter().all(|o| o.sides() == 4 && o.area() == 1));
+        Ok(())
+    }
+}
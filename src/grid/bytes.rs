@@ -0,0 +1,175 @@
+use super::{Direction, Grid, Neighbor, neighbor_directions};
+use crate::error::{Error, Result};
+use crate::math::two_dimensional::Point;
+use alloc::vec::Vec;
+use core::ops::Index;
+
+/// A read-only grid view over raw input bytes, one row per line. Cells are looked up in place at `y * stride + x`, so
+/// building one copies nothing no matter how large the input. `\r\n` line endings and a trailing newline are fine.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ByteGrid<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    /// Fails with [`Error::GridShape`] unless every line is as long as the first.
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        let first = data.split(|&b| b == b'\n').next().unwrap_or_default();
+        let width = first.strip_suffix(b"\r").unwrap_or(first).len();
+        let stride = first.len() + 1;
+
+        let mut height = 0;
+        for line in data.split(|&b| b == b'\n') {
+            let start = height * stride;
+            if start >= data.len() {
+                break;
+            }
+            let text = line.strip_suffix(b"\r").unwrap_or(line);
+            // A row that ends differently (e.g. a lone `\r\n` among `\n`s) would shift every later row's offset
+            let aligned = line.len() + 1 == stride || start + line.len() == data.len();
+            if text.len() != width || !aligned {
+                return Err(Error::GridShape {
+                    row: height,
+                    expected: width,
+                    found: text.len(),
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, position: Point) -> Option<&'a u8> {
+        if position.x < self.width && position.y < self.height {
+            self.data.get(position.y * self.stride + position.x)
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&'a [u8]> {
+        (y < self.height).then(|| &self.data[y * self.stride..][..self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// The first position holding `byte`, scanning row by row.
+    pub fn find(&self, byte: u8) -> Option<Point> {
+        self.positions(byte).next()
+    }
+
+    /// Every position holding `byte`, row by row.
+    pub fn positions(&self, byte: u8) -> impl Iterator<Item = Point> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &b)| b == byte)
+            .map(|(i, _)| Point::new(i % self.stride, i / self.stride))
+            .filter(|p| p.x < self.width)
+    }
+
+    pub fn neighbor_in_direction(&self, direction: Direction, position: Point) -> Option<Neighbor> {
+        let (dx, dy) = direction.delta();
+        let x = position.x.checked_add_signed(dx)?;
+        let y = position.y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then(|| Neighbor::new(direction, Point::new(x, y)))
+    }
+
+    /// Like [`super::neighbors`], without collecting into a `Vec`.
+    pub fn neighbors(
+        &self,
+        position: Point,
+        include_diagonals: bool,
+    ) -> impl Iterator<Item = Neighbor> + '_ {
+        neighbor_directions(include_diagonals)
+            .iter()
+            .filter_map(move |&d| self.neighbor_in_direction(d, position))
+    }
+
+    /// Copies the view into an owned, mutable [`Grid`].
+    pub fn to_grid(&self) -> Grid<u8> {
+        Grid(self.rows().map(<[u8]>::to_vec).collect::<Vec<_>>())
+    }
+}
+
+impl Index<Point> for ByteGrid<'_> {
+    type Output = u8;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::neighbors;
+    use anyhow::Result;
+
+    #[test]
+    fn test_byte_grid() -> Result<()> {
+        let grid = ByteGrid::new(b"#.S\n..#\n.E.\n")?;
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Point::new(2, 1)], b'#');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.row(2), Some(&b".E."[..]));
+        assert_eq!(grid.find(b'S'), Some(Point::new(2, 0)));
+        assert_eq!(grid.find(b'\n'), None);
+        assert_eq!(grid.positions(b'#').count(), 2);
+
+        let owned = grid.to_grid();
+        for point in [Point::new(0, 0), Point::new(1, 2), Point::new(2, 2)] {
+            assert_eq!(owned[point], grid[point]);
+            assert_eq!(
+                grid.neighbors(point, true).collect::<Vec<_>>(),
+                neighbors(&owned, point, true)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_grid_line_endings() -> Result<()> {
+        let unix = ByteGrid::new(b"ab\ncd")?;
+        let windows = ByteGrid::new(b"ab\r\ncd\r\n")?;
+        assert_eq!((windows.width(), windows.height()), (2, 2));
+        assert_eq!(unix.to_grid(), windows.to_grid());
+        assert_eq!(ByteGrid::new(b"ab\r\ncd")?.to_grid(), windows.to_grid());
+        assert!(ByteGrid::new(b"ab\ncd\r\nef").is_err());
+        assert_eq!(windows[Point::new(1, 1)], b'd');
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_grid_ragged() {
+        let err = ByteGrid::new(b"abc\nde\nfgh\n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::GridShape {
+                row: 1,
+                expected: 3,
+                found: 2
+            }
+        ));
+    }
+}
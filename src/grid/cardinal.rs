@@ -0,0 +1,128 @@
+use super::Direction;
+
+/// One of the four orthogonal directions. For walkers, pipes and anything else that can't move diagonally; converts
+/// into a [`Direction`] wherever the 8-way API is needed.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Cardinal {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Cardinal {
+    /// Clockwise from `Up`.
+    pub const ALL: [Cardinal; 4] = [
+        Cardinal::Up,
+        Cardinal::Right,
+        Cardinal::Down,
+        Cardinal::Left,
+    ];
+
+    pub fn delta(self) -> (isize, isize) {
+        Direction::from(self).delta()
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Cardinal::Up => Cardinal::Down,
+            Cardinal::Down => Cardinal::Up,
+            Cardinal::Left => Cardinal::Right,
+            Cardinal::Right => Cardinal::Left,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Cardinal::Up => Cardinal::Right,
+            Cardinal::Right => Cardinal::Down,
+            Cardinal::Down => Cardinal::Left,
+            Cardinal::Left => Cardinal::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Cardinal::Up | Cardinal::Down)
+    }
+}
+
+impl From<Cardinal> for Direction {
+    fn from(value: Cardinal) -> Self {
+        match value {
+            Cardinal::Up => Direction::Up,
+            Cardinal::Down => Direction::Down,
+            Cardinal::Left => Direction::Left,
+            Cardinal::Right => Direction::Right,
+        }
+    }
+}
+
+/// Fails with the original direction if it is diagonal.
+impl TryFrom<Direction> for Cardinal {
+    type Error = Direction;
+
+    fn try_from(value: Direction) -> Result<Self, Self::Error> {
+        match value {
+            Direction::Up => Ok(Cardinal::Up),
+            Direction::Down => Ok(Cardinal::Down),
+            Direction::Left => Ok(Cardinal::Left),
+            Direction::Right => Ok(Cardinal::Right),
+            diagonal => Err(diagonal),
+        }
+    }
+}
+
+/// Accepts both the `UDLR` letters and the `^v<>` arrows used by puzzle inputs.
+impl TryFrom<char> for Cardinal {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | '^' => Ok(Cardinal::Up),
+            'D' | 'v' => Ok(Cardinal::Down),
+            'L' | '<' => Ok(Cardinal::Left),
+            'R' | '>' => Ok(Cardinal::Right),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<Cardinal> for char {
+    fn from(value: Cardinal) -> Self {
+        Direction::from(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinal_turns() {
+        for c in Cardinal::ALL {
+            assert_eq!(c.turn_left().turn_right(), c);
+            assert_eq!(c.turn_right().turn_right(), c.opposite());
+            assert_eq!(Direction::from(c.opposite()), Direction::from(c).opposite());
+        }
+        assert_eq!(Cardinal::Up.turn_left(), Cardinal::Left);
+        assert!(Cardinal::Down.is_vertical());
+    }
+
+    #[test]
+    fn test_cardinal_conversions() {
+        assert_eq!(Cardinal::try_from(Direction::Left), Ok(Cardinal::Left));
+        assert_eq!(
+            Cardinal::try_from(Direction::UpperLeft),
+            Err(Direction::UpperLeft)
+        );
+        assert_eq!(Cardinal::try_from('^'), Ok(Cardinal::Up));
+        assert_eq!(Cardinal::try_from('R'), Ok(Cardinal::Right));
+        assert_eq!(Cardinal::try_from('x'), Err('x'));
+        assert_eq!(char::from(Cardinal::Down), '↓');
+        assert_eq!(Cardinal::Left.delta(), (-1, 0));
+    }
+}
@@ -0,0 +1,89 @@
+use super::{Cardinal, Direction};
+use core::ops::{Index, IndexMut};
+
+/// One value per [`Direction`], stored in an array rather than a `HashMap`, for per-direction state in hot loops
+/// such as which directions a beam has already passed through a cell in. Also indexable by [`Cardinal`]. Values are
+/// stored in [`Direction::ALL`] order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DirectionMap<T>([T; 8]);
+
+impl<T> DirectionMap<T> {
+    /// A map holding `value(direction)` for each direction.
+    pub fn from_fn(mut value: impl FnMut(Direction) -> T) -> Self {
+        Self(Direction::ALL.map(&mut value))
+    }
+
+    /// Each direction with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        Direction::ALL.into_iter().zip(&self.0)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Direction, &mut T)> {
+        Direction::ALL.into_iter().zip(&mut self.0)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+}
+
+impl<T: Clone> DirectionMap<T> {
+    pub fn filled(value: T) -> Self {
+        Self::from_fn(|_| value.clone())
+    }
+}
+
+impl<T> Index<Direction> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, direction: Direction) -> &T {
+        &self.0[direction as usize]
+    }
+}
+
+impl<T> IndexMut<Direction> for DirectionMap<T> {
+    fn index_mut(&mut self, direction: Direction) -> &mut T {
+        &mut self.0[direction as usize]
+    }
+}
+
+impl<T> Index<Cardinal> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, cardinal: Cardinal) -> &T {
+        &self[Direction::from(cardinal)]
+    }
+}
+
+impl<T> IndexMut<Cardinal> for DirectionMap<T> {
+    fn index_mut(&mut self, cardinal: Cardinal) -> &mut T {
+        &mut self[Direction::from(cardinal)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_map() {
+        for (i, d) in Direction::ALL.into_iter().enumerate() {
+            assert_eq!(d as usize, i);
+        }
+
+        let mut visited = DirectionMap::<bool>::default();
+        visited[Direction::Up] = true;
+        visited[Cardinal::Left] = true;
+        assert!(visited[Cardinal::Up]);
+        let set = visited
+            .iter()
+            .filter(|&(_, &v)| v)
+            .map(|(d, _)| d)
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(set, [Direction::Up, Direction::Left]);
+
+        let deltas = DirectionMap::from_fn(Direction::delta);
+        assert_eq!(deltas[Direction::LowerLeft], (-1, 1));
+        assert_eq!(DirectionMap::filled(3).values().sum::<i32>(), 24);
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Grid, neighbors};
+use crate::math::two_dimensional::Point;
+use alloc::collections::VecDeque;
+use alloc::vec;
+
+impl<T> Grid<T> {
+    /// The number of orthogonal steps from every cell to the nearest of `sources`, by one breadth-first search from
+    /// all of them at once, moving only onto cells for which `passable` returns true. Sources are at distance 0
+    /// whether passable or not, and cells no source can reach are `None`. Sources outside the grid are ignored.
+    pub fn distance_map<I, P>(&self, sources: I, mut passable: P) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Point>,
+        P: FnMut(Point, &T) -> bool,
+    {
+        let mut distances = Grid(vec![vec![None; self.width()]; self.height()]);
+        let mut queue = VecDeque::new();
+        for source in sources {
+            if let Some(distance @ None) = distances.get_mut(source) {
+                *distance = Some(0);
+                queue.push_back(source);
+            }
+        }
+
+        while let Some(position) = queue.pop_front() {
+            let next = distances[position].map(|d| d + 1);
+            for neighbor in neighbors(self, position, false) {
+                let to = neighbor.position;
+                if distances[to].is_none() && passable(to, &self[to]) {
+                    distances[to] = next;
+                    queue.push_back(to);
+                }
+            }
+        }
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_distance_map() {
+        let grid = Grid::try_from(vec![
+            "..#..".chars().collect::<Vec<_>>(),
+            "..#..".chars().collect(),
+            ".....".chars().collect(),
+        ])
+        .unwrap();
+
+        let distances = grid.distance_map([Point::new(0, 0)], |_, &c| c != '#');
+        assert_eq!(distances[Point::new(1, 1)], Some(2));
+        assert_eq!(distances[Point::new(2, 0)], None);
+        assert_eq!(distances[Point::new(4, 0)], Some(8));
+
+        let distances = grid.distance_map([Point::new(0, 0), Point::new(4, 0)], |_, &c| c != '#');
+        assert_eq!(distances[Point::new(3, 0)], Some(1));
+        assert_eq!(distances[Point::new(2, 2)], Some(4));
+        assert_eq!(distances[Point::new(0, 2)], Some(2));
+
+        let distances = grid.distance_map([Point::new(9, 9)], |_, _| true);
+        assert!(distances.iter().flatten().all(Option::is_none));
+    }
+}
@@ -0,0 +1,148 @@
+use super::{Grid, neighbors};
+use crate::graph::Graph;
+use crate::math::two_dimensional::Point;
+
+impl<T> Grid<T> {
+    /// Builds a directed graph of the cells for which `passable` returns true, with an edge between orthogonally
+    /// adjacent passable cells whenever `edge_cost(from, to)` returns a cost. Return the same cost both ways for
+    /// free movement, or `None` one way for one-way cells such as slopes.
+    ///
+    /// Large mazes can be shrunk with [`Graph::contract_corridors`].
+    pub fn to_graph<P, C>(&self, mut passable: P, mut edge_cost: C) -> Graph<Point, u64>
+    where
+        P: FnMut(Point, &T) -> bool,
+        C: FnMut(Point, Point) -> Option<u64>,
+    {
+        let mut graph = Graph::directed();
+        for (y, row) in self.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let from = Point::new(x, y);
+                if !passable(from, cell) {
+                    continue;
+                }
+                graph.add_node(from);
+
+                for neighbor in neighbors(self, from, false) {
+                    let to = neighbor.position;
+                    if !passable(to, &self[to]) {
+                        continue;
+                    }
+                    if let Some(cost) = edge_cost(from, to) {
+                        graph.add_edge(from, to, cost);
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction;
+
+    const HIKE: [&str; 23] = [
+        "#.#####################",
+        "#.......#########...###",
+        "#######.#########.#.###",
+        "###.....#.>.>.###.#.###",
+        "###v#####.#v#.###.#.###",
+        "###.>...#.#.#.....#...#",
+        "###v###.#.#.#########.#",
+        "###...#.#.#.......#...#",
+        "#####.#.#.#######.#.###",
+        "#.....#.#.#.......#...#",
+        "#.#####.#.#.#########v#",
+        "#.#...#...#...###...>.#",
+        "#.#.#v#######v###.###v#",
+        "#...#.>.#...>.>.#.###.#",
+        "#####v#.#.###v#.#.###.#",
+        "#.....#...#...#.#.#...#",
+        "#.#########.###.#.#.###",
+        "#...###...#...#...#.###",
+        "###.###.#.###v#####v###",
+        "#...#...#.#.>.>.#.>.###",
+        "#.###.###.#.###.#.#v###",
+        "#.....###...###...#...#",
+        "#####################.#",
+    ];
+
+    fn hike(slippery: bool) -> (Graph<Point, u64>, usize, usize) {
+        let grid = Grid::try_from(
+            HIKE.iter()
+                .map(|r| r.chars().collect())
+                .collect::<Vec<Vec<char>>>(),
+        )
+        .unwrap();
+        let graph = grid.to_graph(
+            |_, &c| c != '#',
+            |from, to| {
+                let downhill = match grid[from] {
+                    '>' => Direction::Right,
+                    'v' => Direction::Down,
+                    _ => return Some(1),
+                };
+                let (dx, dy) = downhill.delta();
+                let allowed = !slippery
+                    || from.x.checked_add_signed(dx) == Some(to.x)
+                        && from.y.checked_add_signed(dy) == Some(to.y);
+                allowed.then_some(1)
+            },
+        );
+
+        let (start, goal) = (Point::new(1, 0), Point::new(21, 22));
+        let graph = graph.contract_corridors(|&p| p == start || p == goal);
+        let (start, goal) = (graph.index(&start).unwrap(), graph.index(&goal).unwrap());
+        (graph, start, goal)
+    }
+
+    fn longest_simple_path(
+        graph: &Graph<Point, u64>,
+        at: usize,
+        goal: usize,
+        seen: &mut Vec<bool>,
+    ) -> Option<u64> {
+        if at == goal {
+            return Some(0);
+        }
+        seen[at] = true;
+        let mut best = None;
+        for (n, &w) in graph.neighbors(at) {
+            if !seen[n]
+                && let Some(rest) = longest_simple_path(graph, n, goal, seen)
+            {
+                best = best.max(Some(w + rest));
+            }
+        }
+        seen[at] = false;
+        best
+    }
+
+    #[test]
+    fn test_to_graph() {
+        let grid = Grid::try_from(vec![vec!['.', '#'], vec!['.', '.']]).unwrap();
+        let graph = grid.to_graph(|_, &c| c == '.', |_, _| Some(1));
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.edge_count(), 4);
+        assert!(graph.has_edge(&Point::new(0, 1), &Point::new(1, 1)));
+        assert!(!graph.has_edge(&Point::new(0, 0), &Point::new(1, 0)));
+    }
+
+    #[test]
+    fn test_to_graph_hike() {
+        let (graph, start, goal) = hike(true);
+        assert_eq!(
+            graph.longest_path(start, goal, |&w| w).unwrap().unwrap().0,
+            94
+        );
+
+        let (graph, start, goal) = hike(false);
+        assert_eq!(graph.len(), 9);
+        let mut seen = vec![false; graph.len()];
+        assert_eq!(
+            longest_simple_path(&graph, start, goal, &mut seen),
+            Some(154)
+        );
+    }
+}
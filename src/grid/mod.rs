@@ -1,8 +1,44 @@
+use crate::error::{Error, Result};
 use crate::math::two_dimensional::Point;
-use anyhow::{Result, bail};
-use std::fmt::{Debug, Display};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use core::fmt::Display;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+#[cfg(feature = "std")]
 use std::io::Write;
-use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+mod array;
+#[cfg(feature = "std")]
+mod boundary;
+mod bytes;
+mod cardinal;
+mod direction_map;
+mod distance;
+#[cfg(feature = "std")]
+mod graph;
+mod ocr;
+mod pattern;
+mod push;
+mod symmetry;
+mod tilt;
+#[cfg(feature = "std")]
+mod turtle;
+mod voronoi;
+
+pub use array::ArrayGrid;
+#[cfg(feature = "std")]
+pub use boundary::Outline;
+pub use bytes::ByteGrid;
+pub use cardinal::Cardinal;
+pub use direction_map::DirectionMap;
+pub use ocr::ocr;
+pub use push::Pushable;
+#[cfg(feature = "std")]
+pub use turtle::Turtle;
+pub use voronoi::{Nearest, Voronoi};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grid<T>(Vec<Vec<T>>);
@@ -23,19 +59,116 @@ impl<T> Grid<T> {
     pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
         self.0.get_mut(position.y)?.get_mut(position.x)
     }
+
+    /// Builds a grid from text lines, mapping each character with `cell`. With [`YAxis::Up`], the last line becomes
+    /// row 0, so `Point::new(0, 0)` is the bottom-left corner.
+    pub fn from_lines<S, F>(lines: &[S], y_axis: YAxis, mut cell: F) -> Result<Self>
+    where
+        S: AsRef<str>,
+        F: FnMut(char) -> T,
+    {
+        let mut rows = lines
+            .iter()
+            .map(|line| line.as_ref().chars().map(&mut cell).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if y_axis == YAxis::Up {
+            rows.reverse();
+        }
+        Self::try_from(rows)
+    }
+
+    /// The in-bounds neighbors of `position` whose values satisfy `keep`, e.g. the open cells for a BFS step.
+    pub fn neighbors_where<'a, F>(
+        &'a self,
+        position: Point,
+        include_diagonals: bool,
+        mut keep: F,
+    ) -> impl Iterator<Item = Neighbor> + 'a
+    where
+        F: FnMut(&T) -> bool + 'a,
+    {
+        neighbor_directions(include_diagonals)
+            .iter()
+            .filter_map(move |&d| neighbor_in_direction(self, d, position))
+            .filter(move |n| keep(&self[n.position]))
+    }
+
+    /// Rotates the grid a quarter turn clockwise. Square grids are rotated in place by swapping cells around each
+    /// ring, so repeated spins don't allocate; other grids are rebuilt with their dimensions swapped.
+    pub fn rotate_square_clockwise(&mut self) {
+        let n = self.height();
+        if n != self.width() {
+            let rows = core::mem::take(&mut self.0);
+            self.0 = rotated_clockwise(rows);
+            return;
+        }
+
+        for ring in 0..n / 2 {
+            let last = n - 1 - ring;
+            for j in ring..last {
+                let top = (ring, j);
+                let right = (j, last);
+                let bottom = (last, n - 1 - j);
+                let left = (n - 1 - j, ring);
+                // left moves to top, bottom to left, right to bottom, and top to right
+                swap_cells(&mut self.0, top, left);
+                swap_cells(&mut self.0, left, bottom);
+                swap_cells(&mut self.0, bottom, right);
+            }
+        }
+    }
+}
+
+fn rotated_clockwise<T>(rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let width = rows.first().map_or(0, Vec::len);
+    let mut columns = rows
+        .into_iter()
+        .rev()
+        .map(Vec::into_iter)
+        .collect::<Vec<_>>();
+    (0..width)
+        .map(|_| columns.iter_mut().filter_map(Iterator::next).collect())
+        .collect()
+}
+
+/// Swaps two cells, given as (row, column), which may be in different rows.
+fn swap_cells<T>(rows: &mut [Vec<T>], (r1, c1): (usize, usize), (r2, c2): (usize, usize)) {
+    if r1 == r2 {
+        rows[r1].swap(c1, c2);
+    } else {
+        let (lo, hi) = (r1.min(r2), r1.max(r2));
+        let (head, tail) = rows.split_at_mut(hi);
+        let (a, b) = (&mut head[lo], &mut tail[0]);
+        let (ca, cb) = if r1 < r2 { (c1, c2) } else { (c2, c1) };
+        core::mem::swap(&mut a[ca], &mut b[cb]);
+    }
+}
+
+/// Which way `y` grows when reading a grid from text: down the page, as most puzzles state it, or up, with the first
+/// row at the bottom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    #[default]
+    Down,
+    Up,
 }
 
 impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     fn try_from(data: Vec<Vec<T>>) -> Result<Self> {
-        let mut row_len = None;
-        for row in data.iter() {
-            match row_len {
-                None => row_len = Some(row.len()),
-                Some(len) if len != row.len() => bail!("Rows must be the same length"),
-                _ => (),
-            }
+        let expected = data.first().map_or(0, Vec::len);
+        if let Some((row, found)) = data
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .find(|&(_, len)| len != expected)
+        {
+            return Err(Error::GridShape {
+                row,
+                expected,
+                found,
+            });
         }
 
         Ok(Self(data))
@@ -71,7 +204,7 @@ impl<T> IndexMut<Point> for Grid<T> {
 }
 
 pub struct Rows<'a, T> {
-    inner: std::slice::Iter<'a, Vec<T>>,
+    inner: core::slice::Iter<'a, Vec<T>>,
 }
 
 impl<'a, T> Iterator for Rows<'a, T> {
@@ -83,7 +216,7 @@ impl<'a, T> Iterator for Rows<'a, T> {
 }
 
 pub struct RowsMut<'a, T> {
-    inner: std::slice::IterMut<'a, Vec<T>>,
+    inner: core::slice::IterMut<'a, Vec<T>>,
 }
 
 impl<'a, T> Iterator for RowsMut<'a, T> {
@@ -116,7 +249,7 @@ impl<'a, T> IntoIterator for &'a mut Grid<T> {
     }
 }
 
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Direction {
     Up,
     Down,
@@ -129,6 +262,18 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Every direction in declaration order: the four cardinal ones, then the diagonals.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpperRight,
+        Direction::UpperLeft,
+        Direction::LowerRight,
+        Direction::LowerLeft,
+    ];
+
     pub fn delta(self) -> (isize, isize) {
         match self {
             Direction::Up => (0, -1),
@@ -141,6 +286,19 @@ impl Direction {
             Direction::LowerRight => (1, 1),
         }
     }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::UpperLeft => Direction::LowerRight,
+            Direction::UpperRight => Direction::LowerLeft,
+            Direction::LowerLeft => Direction::UpperRight,
+            Direction::LowerRight => Direction::UpperLeft,
+        }
+    }
 }
 
 impl From<Direction> for char {
@@ -158,7 +316,7 @@ impl From<Direction> for char {
     }
 }
 
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct Neighbor {
     pub direction: Direction,
     pub position: Point,
@@ -202,29 +360,48 @@ pub fn neighbor_in_direction<T>(
         .map(|_| Neighbor::new(direction, new_position))
 }
 
-pub fn neighbors<T>(grid: &Grid<T>, position: Point, include_diagonals: bool) -> Vec<Neighbor> {
-    let mut directions = vec![
+/// The directions [`neighbors`] checks, in order: the cardinal ones, then (with diagonals) the rest.
+pub(crate) fn neighbor_directions(include_diagonals: bool) -> &'static [Direction] {
+    // not `Direction::ALL`: the diagonals are checked in this order, which callers may rely on for tie-breaking
+    const DIRECTIONS: [Direction; 8] = [
         Direction::Up,
         Direction::Down,
         Direction::Left,
         Direction::Right,
+        Direction::UpperLeft,
+        Direction::UpperRight,
+        Direction::LowerLeft,
+        Direction::LowerRight,
     ];
 
     if include_diagonals {
-        directions.extend_from_slice(&[
-            Direction::UpperLeft,
-            Direction::UpperRight,
-            Direction::LowerLeft,
-            Direction::LowerRight,
-        ]);
+        &DIRECTIONS
+    } else {
+        &DIRECTIONS[..4]
     }
+}
 
-    directions
-        .into_iter()
-        .filter_map(|d| neighbor_in_direction(grid, d, position))
+pub fn neighbors<T>(grid: &Grid<T>, position: Point, include_diagonals: bool) -> Vec<Neighbor> {
+    neighbor_directions(include_diagonals)
+        .iter()
+        .filter_map(|&d| neighbor_in_direction(grid, d, position))
+        .collect()
+}
+
+/// [`neighbors`] collected on the stack instead of into a heap-allocated `Vec`.
+#[cfg(feature = "smallvec")]
+pub fn neighbors_inline<T>(
+    grid: &Grid<T>,
+    position: Point,
+    include_diagonals: bool,
+) -> smallvec::SmallVec<[Neighbor; 8]> {
+    neighbor_directions(include_diagonals)
+        .iter()
+        .filter_map(|&d| neighbor_in_direction(grid, d, position))
         .collect()
 }
 
+#[cfg(feature = "std")]
 pub fn print_grid<T, F, O, W>(grid: &Grid<T>, mapper: F, writer: &mut W) -> std::io::Result<()>
 where
     F: Fn(&T) -> O,
@@ -240,9 +417,55 @@ where
     Ok(())
 }
 
+/// Logs the grid at trace level, rendered with [`print_grid`], under an optional format-style header. Nothing is
+/// rendered unless trace logging is enabled for the calling module.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! trace_grid {
+    ($grid:expr, $mapper:expr $(,)?) => {
+        $crate::trace_grid!($grid, $mapper, "")
+    };
+    ($grid:expr, $mapper:expr, $($header:tt)+) => {
+        if $crate::__private::log::log_enabled!($crate::__private::log::Level::Trace) {
+            let mut rendered = Vec::new();
+            $crate::grid::print_grid(&$grid, $mapper, &mut rendered).expect("writing to a Vec can't fail");
+            $crate::__private::log::trace!(
+                "{}\n{}",
+                format_args!($($header)+),
+                String::from_utf8_lossy(&rendered).trim_end()
+            );
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+    use anyhow::Result;
+
+    #[test]
+    fn test_neighbor_order() -> Result<()> {
+        let grid = Grid::try_from(vec![vec![0; 3]; 3])?;
+        let directions = neighbors(&grid, Point::new(1, 1), true)
+            .into_iter()
+            .map(|n| n.direction)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            directions,
+            [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+                Direction::UpperLeft,
+                Direction::UpperRight,
+                Direction::LowerLeft,
+                Direction::LowerRight,
+            ]
+        );
+        Ok(())
+    }
 
     #[test]
     fn test_neighbors() -> Result<()> {
@@ -448,6 +671,21 @@ mod tests {
         assert_eq!(Direction::LowerRight.delta(), (1, 1));
     }
 
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_neighbors_inline() -> Result<()> {
+        let grid = Grid::try_from(vec![vec![0; 4]; 3])?;
+        for position in [Point::new(0, 0), Point::new(1, 1), Point::new(3, 2)] {
+            for diagonals in [false, true] {
+                let inline = neighbors_inline(&grid, position, diagonals);
+                assert!(!inline.spilled());
+                assert_eq!(inline.as_slice(), neighbors(&grid, position, diagonals));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_print_grid_to_writer() -> Result<()> {
         let grid = Grid::try_from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]])?;
@@ -529,6 +767,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_grid_ragged_rows() {
+        let err = Grid::try_from(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::GridShape {
+                row: 2,
+                expected: 2,
+                found: 1
+            }
+        ));
+    }
+
     #[test]
     fn test_grid_get() -> Result<()> {
         let grid = Grid::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]])?;
@@ -589,4 +840,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_trace_grid() -> Result<()> {
+        crate::logging::init_test_logger()?;
+        let grid = Grid::try_from(vec![vec![true, false], vec![false, true]])?;
+        trace_grid!(grid, |&on| if on { '#' } else { '.' });
+        trace_grid!(grid, |&on| u8::from(on), "step {}", 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_lines() -> Result<()> {
+        let lines = ["ab", "cd"];
+        let down = Grid::from_lines(&lines, YAxis::Down, |c| c)?;
+        assert_eq!(down[Point::new(0, 0)], 'a');
+        let up = Grid::from_lines(&lines, YAxis::Up, |c| c.to_ascii_uppercase())?;
+        assert_eq!(up[Point::new(0, 0)], 'C');
+        assert_eq!(up[Point::new(1, 1)], 'B');
+        assert!(Grid::from_lines(&["ab", "c"], YAxis::Up, |c| c).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_square_clockwise() -> Result<()> {
+        for n in 0..6 {
+            let cells = (0..n)
+                .map(|y| (0..n).map(|x| y * n + x).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let mut grid = Grid::try_from(cells.clone())?;
+            grid.rotate_square_clockwise();
+            let expected = Grid::try_from(rotated_clockwise(cells))?;
+            assert_eq!(grid, expected, "{n}x{n}");
+        }
+
+        let mut grid = Grid::from_lines(&["abc", "def"], YAxis::Down, |c| c)?;
+        grid.rotate_square_clockwise();
+        assert_eq!(
+            grid,
+            Grid::from_lines(&["da", "eb", "fc"], YAxis::Down, |c| c)?
+        );
+        for _ in 0..3 {
+            grid.rotate_square_clockwise();
+        }
+        assert_eq!(grid, Grid::from_lines(&["abc", "def"], YAxis::Down, |c| c)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors_where() -> Result<()> {
+        let grid = Grid::from_lines(&["#.#", "...", "##."], YAxis::Down, |c| c)?;
+        let open = grid
+            .neighbors_where(Point::new(1, 1), false, |&c| c == '.')
+            .map(|n| n.direction)
+            .collect::<Vec<_>>();
+        assert_eq!(open, [Direction::Up, Direction::Left, Direction::Right]);
+        assert_eq!(
+            grid.neighbors_where(Point::new(1, 1), true, |&c| c == '.')
+                .count(),
+            4
+        );
+        Ok(())
+    }
 }
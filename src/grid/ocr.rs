@@ -0,0 +1,221 @@
+use super::Grid;
+use crate::error::{Error, Result};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const SMALL_HEIGHT: usize = 6;
+const LARGE_HEIGHT: usize = 10;
+
+const SMALL_GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE_GLYPHS: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the capital letters drawn by lit cells in `grid`, using either the 4x6 or the 6x10 Advent of Code font.
+/// Letters must be separated by at least one unlit column.
+pub fn ocr(grid: &Grid<bool>) -> Result<String> {
+    let lit_rows = (0..grid.height())
+        .filter(|&y| grid.0[y].iter().any(|&lit| lit))
+        .collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Ok(String::new());
+    };
+
+    let rows = &grid.0[top..=bottom];
+    let glyphs: &[(char, &str)] = match rows.len() {
+        SMALL_HEIGHT => &SMALL_GLYPHS,
+        LARGE_HEIGHT => &LARGE_GLYPHS,
+        height => {
+            return Err(Error::parse(format!("Unsupported letter height: {height}")));
+        }
+    };
+
+    let column_lit = |x: usize| rows.iter().any(|row| row[x]);
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if !column_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < grid.width() && column_lit(x) {
+            x += 1;
+        }
+
+        let rendered = rows
+            .iter()
+            .map(|row| {
+                row[start..x]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        match glyphs.iter().find(|(_, glyph)| *glyph == rendered) {
+            Some((letter, _)) => letters.push(*letter),
+            None => {
+                return Err(Error::parse(format!(
+                    "Unrecognized letter at column {start}:\n{rendered}"
+                )));
+            }
+        }
+    }
+
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Grid<bool>> {
+        Grid::try_from(
+            s.lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect::<Vec<Vec<_>>>(),
+        )
+    }
+
+    #[test]
+    fn test_ocr_small() -> Result<()> {
+        let grid = parse(
+            "\
+#..#.####.###..###..
+#..#.#....#..#.#..#.
+####.###..###..#..#.
+#..#.#....#..#.###..
+#..#.#....#..#.#.#..
+#..#.####.###..#..#.",
+        )?;
+        assert_eq!(ocr(&grid)?, "HEBR");
+        Ok(())
+    }
+
+    #[test]
+    fn test_ocr_small_narrow_letters() -> Result<()> {
+        let grid = parse(
+            "\
+......................
+.###..#...#.####......
+..#...#...#....#......
+..#....#.#....#.......
+..#.....#....#........
+..#.....#...#.........
+.###....#...####......",
+        )?;
+        assert_eq!(ocr(&grid)?, "IYZ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_ocr_large() -> Result<()> {
+        let grid = parse(
+            "\
+#....#..#####.
+#....#..#....#
+.#..#...#....#
+.#..#...#....#
+..##....#####.
+..##....#.....
+.#..#...#.....
+.#..#...#.....
+#....#..#.....
+#....#..#.....",
+        )?;
+        assert_eq!(ocr(&grid)?, "XP");
+        Ok(())
+    }
+
+    #[test]
+    fn test_ocr_errors() -> Result<()> {
+        assert_eq!(ocr(&parse("....\n....")?)?, "");
+        assert!(ocr(&parse("#.#\n.#.\n#.#")?).is_err());
+        assert!(ocr(&parse("##\n##\n##\n##\n##\n##")?).is_err());
+        Ok(())
+    }
+}
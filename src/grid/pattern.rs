@@ -0,0 +1,122 @@
+use super::{Grid, rotated_clockwise};
+use crate::math::two_dimensional::Point;
+use alloc::vec::Vec;
+
+impl<T: Clone> Grid<T> {
+    /// The grid's eight orientations: the four quarter turns clockwise, starting from the grid as it is, then the
+    /// same four of its mirror image (each row reversed). Symmetric grids repeat some orientations.
+    pub fn orientations(&self) -> [Grid<T>; 8] {
+        let mirrored = self
+            .0
+            .iter()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect();
+        let mut rows = [self.0.clone(), mirrored];
+        core::array::from_fn(|i| {
+            let turned = &mut rows[i / 4];
+            let grid = Grid(turned.clone());
+            *turned = rotated_clockwise(core::mem::take(turned));
+            grid
+        })
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The top-left corners of every placement of `pattern` inside this grid where each `Some` cell of the pattern
+    /// equals the cell under it. `None` cells match anything. Matches may overlap.
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<Point> {
+        let (width, height) = (pattern.width(), pattern.height());
+        if width > self.width() || height > self.height() {
+            return Vec::new();
+        }
+
+        let cells = pattern
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(x, cell)| Some((x, y, cell.as_ref()?)))
+            })
+            .collect::<Vec<_>>();
+        (0..=self.height() - height)
+            .flat_map(|top| (0..=self.width() - width).map(move |left| Point::new(left, top)))
+            .filter(|corner| {
+                cells
+                    .iter()
+                    .all(|&(x, y, cell)| self.0[corner.y + y][corner.x + x] == *cell)
+            })
+            .collect()
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// [`find_pattern`](Self::find_pattern) for each of the pattern's [`orientations`](Self::orientations), as
+    /// `(orientation, corner)` pairs where `orientation` indexes `pattern.orientations()`.
+    pub fn find_pattern_oriented(&self, pattern: &Grid<Option<T>>) -> Vec<(usize, Point)> {
+        pattern
+            .orientations()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, oriented)| {
+                self.find_pattern(oriented)
+                    .into_iter()
+                    .map(move |corner| (i, corner))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+
+    const MONSTER: [&str; 3] = [
+        "                  # ",
+        "#    ##    ##    ###",
+        " #  #  #  #  #  #   ",
+    ];
+
+    fn monster() -> Grid<Option<char>> {
+        Grid::from_lines(&MONSTER, YAxis::Down, |c| (c == '#').then_some('#')).unwrap()
+    }
+
+    #[test]
+    fn test_orientations() {
+        let grid = Grid::from_lines(&["ab", "cd"], YAxis::Down, |c| c).unwrap();
+        let orientations = grid.orientations();
+        assert_eq!(orientations[0], grid);
+        assert_eq!(orientations[1].0, [['c', 'a'], ['d', 'b']]);
+        assert_eq!(orientations[4].0, [['b', 'a'], ['d', 'c']]);
+        for i in 0..8 {
+            assert!(orientations[..i].iter().all(|o| *o != orientations[i]));
+        }
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = Grid::from_lines(&["#.#", ".#.", "#.#"], YAxis::Down, |c| c).unwrap();
+        let pattern =
+            Grid::from_lines(&["#?", "?#"], YAxis::Down, |c| (c != '?').then_some(c)).unwrap();
+        assert_eq!(
+            grid.find_pattern(&pattern),
+            [Point::new(0, 0), Point::new(1, 1)]
+        );
+        assert!(grid.find_pattern(&monster()).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_oriented() {
+        let mut lines = MONSTER.map(|l| l.replace(' ', "."));
+        lines.reverse();
+        let sea = Grid::from_lines(&lines, YAxis::Down, |c| c).unwrap();
+        assert!(sea.find_pattern(&monster()).is_empty());
+        // upside down is a half turn of the mirror image
+        assert_eq!(
+            sea.find_pattern_oriented(&monster()),
+            [(6, Point::new(0, 0))]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use super::{Cardinal, Grid, neighbor_in_direction, swap_cells};
+use crate::math::two_dimensional::Point;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// How a cell takes part in [`Grid::push`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pushable {
+    /// Free space that something can move into
+    Open,
+    /// Stops the push, as does the edge of the grid
+    Wall,
+    /// A one-cell box
+    Box,
+    /// The left half of a two-cell box
+    BoxLeft,
+    /// The right half of a two-cell box
+    BoxRight,
+}
+
+impl<T> Grid<T> {
+    /// Moves whatever is at `from` one step in `heading`, pushing any boxes in the way, which push the boxes in
+    /// theirs. The two halves of a wide box always move together, so pushing one up or down can shift a whole
+    /// pyramid of boxes. If anything would be pushed into a wall or off the grid, nothing moves and this returns
+    /// `false`.
+    ///
+    /// Moved cells swap places with the open cells they move into, so every vacated cell ends up holding an open one.
+    pub fn push<F>(&mut self, from: Point, heading: Cardinal, mut kind: F) -> bool
+    where
+        F: FnMut(&T) -> Pushable,
+    {
+        let mut moving = BTreeSet::from([from]);
+        let mut pending = vec![from];
+        while let Some(position) = pending.pop() {
+            let Some(next) = neighbor_in_direction(self, heading.into(), position) else {
+                return false;
+            };
+            let next = next.position;
+            if moving.contains(&next) {
+                continue;
+            }
+            let other_half = match kind(&self[next]) {
+                Pushable::Open => continue,
+                Pushable::Wall => return false,
+                Pushable::Box => None,
+                Pushable::BoxLeft => Some(Cardinal::Right),
+                Pushable::BoxRight => Some(Cardinal::Left),
+            };
+            moving.insert(next);
+            pending.push(next);
+            // sideways, the other half is pushed along anyway; up or down, it has to be pushed too
+            if let Some(side) = other_half.filter(|_| heading.is_vertical())
+                && let Some(half) = neighbor_in_direction(self, side.into(), next)
+                && moving.insert(half.position)
+            {
+                pending.push(half.position);
+            }
+        }
+
+        // the cells furthest along go first, so every cell moves into one that is already open
+        let (dx, dy) = heading.delta();
+        let mut moving = moving.into_iter().collect::<Vec<_>>();
+        moving.sort_by_key(|p| -(p.x as isize * dx + p.y as isize * dy));
+        for position in moving {
+            let to = Point::new(
+                position.x.wrapping_add_signed(dx),
+                position.y.wrapping_add_signed(dy),
+            );
+            swap_cells(&mut self.0, (position.y, position.x), (to.y, to.x));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+    use alloc::string::String;
+    use anyhow::Result;
+
+    fn rows(grid: &Grid<char>) -> Vec<String> {
+        grid.iter().map(|row| row.iter().collect()).collect()
+    }
+
+    fn kind(c: &char) -> Pushable {
+        match c {
+            '#' => Pushable::Wall,
+            'O' => Pushable::Box,
+            '[' => Pushable::BoxLeft,
+            ']' => Pushable::BoxRight,
+            _ => Pushable::Open,
+        }
+    }
+
+    #[test]
+    fn test_push_line() -> Result<()> {
+        let mut grid = Grid::from_lines(&["#.OO@"], YAxis::Down, |c| c)?;
+        assert!(grid.push(Point::new(4, 0), Cardinal::Left, kind));
+        assert_eq!(rows(&grid), ["#OO@."]);
+        assert!(!grid.push(Point::new(3, 0), Cardinal::Left, kind));
+        assert_eq!(rows(&grid), ["#OO@."]);
+        assert!(!grid.push(Point::new(3, 0), Cardinal::Up, kind));
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_wide_boxes() -> Result<()> {
+        let mut grid = Grid::from_lines(
+            &["#######", "#.....#", "#.[]..#", "#..[].#", "#..@..#"],
+            YAxis::Down,
+            |c| c,
+        )?;
+        assert!(grid.push(Point::new(3, 4), Cardinal::Up, kind));
+        assert_eq!(
+            rows(&grid),
+            ["#######", "#.[]..#", "#..[].#", "#..@..#", "#.....#"]
+        );
+
+        // the top box is against the wall now, so the whole stack stays put
+        assert!(!grid.push(Point::new(3, 3), Cardinal::Up, kind));
+        assert_eq!(
+            rows(&grid),
+            ["#######", "#.[]..#", "#..[].#", "#..@..#", "#.....#"]
+        );
+
+        let mut grid = Grid::from_lines(&["#@[][].#"], YAxis::Down, |c| c)?;
+        assert!(grid.push(Point::new(1, 0), Cardinal::Right, kind));
+        assert_eq!(rows(&grid), ["#.@[][]#"]);
+        Ok(())
+    }
+}
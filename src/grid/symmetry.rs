@@ -0,0 +1,100 @@
+use super::Grid;
+use alloc::vec::Vec;
+
+impl<T: PartialEq> Grid<T> {
+    /// Every horizontal mirror line across which the rows reflect with exactly `smudges` mismatched cells, as the
+    /// number of rows above the line. Rows past either edge of the grid are ignored, so a line only needs the shorter
+    /// side to match. A line 0 or `height` away from the top reflects nothing and is never returned.
+    pub fn horizontal_reflections(&self, smudges: usize) -> Vec<usize> {
+        reflections(self.height(), smudges, |a, b| {
+            self.0[a]
+                .iter()
+                .zip(&self.0[b])
+                .filter(|(x, y)| x != y)
+                .count()
+        })
+    }
+
+    /// Every vertical mirror line across which the columns reflect with exactly `smudges` mismatched cells, as the
+    /// number of columns left of the line. See [`horizontal_reflections`](Self::horizontal_reflections).
+    pub fn vertical_reflections(&self, smudges: usize) -> Vec<usize> {
+        reflections(self.width(), smudges, |a, b| {
+            self.0.iter().filter(|row| row[a] != row[b]).count()
+        })
+    }
+}
+
+/// The lines among `len` rows or columns that reflect with exactly `smudges` differences, where `differences(a, b)`
+/// counts the mismatches between lines `a` and `b`.
+fn reflections<F>(len: usize, smudges: usize, mut differences: F) -> Vec<usize>
+where
+    F: FnMut(usize, usize) -> usize,
+{
+    (1..len)
+        .filter(|&line| {
+            let mut found = 0;
+            for i in 0..line.min(len - line) {
+                found += differences(line - 1 - i, line + i);
+                if found > smudges {
+                    return false;
+                }
+            }
+            found == smudges
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+    use anyhow::Result;
+
+    #[test]
+    fn test_reflections() -> Result<()> {
+        let first = Grid::from_lines(
+            &[
+                "#.##..##.",
+                "..#.##.#.",
+                "##......#",
+                "##......#",
+                "..#.##.#.",
+                "..##..##.",
+                "#.#.##.#.",
+            ],
+            YAxis::Down,
+            |c| c,
+        )?;
+        assert_eq!(first.vertical_reflections(0), [5]);
+        assert!(first.horizontal_reflections(0).is_empty());
+        assert_eq!(first.horizontal_reflections(1), [3]);
+        assert!(first.vertical_reflections(1).is_empty());
+
+        let second = Grid::from_lines(
+            &[
+                "#...##..#",
+                "#....#..#",
+                "..##..###",
+                "#####.##.",
+                "#####.##.",
+                "..##..###",
+                "#....#..#",
+            ],
+            YAxis::Down,
+            |c| c,
+        )?;
+        assert_eq!(second.horizontal_reflections(0), [4]);
+        assert!(second.vertical_reflections(0).is_empty());
+        assert_eq!(second.horizontal_reflections(1), [1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reflections_several_lines() -> Result<()> {
+        let grid = Grid::from_lines(&["aaaa"], YAxis::Down, |c| c)?;
+        assert_eq!(grid.vertical_reflections(0), [1, 2, 3]);
+        assert!(grid.horizontal_reflections(0).is_empty());
+        assert!(grid.vertical_reflections(2).is_empty());
+        Ok(())
+    }
+}
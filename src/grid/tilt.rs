@@ -0,0 +1,123 @@
+use super::{Cardinal, Grid, swap_cells};
+
+impl<T> Grid<T> {
+    /// Slides every movable cell as far as it goes in `heading`, like rolling rocks on a tilted platform. Movable cells
+    /// stop at the grid's edge, a blocker, or another movable cell that has stopped; all other cells are empty space
+    /// and are swapped back into the gaps left behind. Each cell is visited once and nothing is allocated.
+    pub fn tilt<M, B>(&mut self, heading: Cardinal, mut movable: M, mut blocker: B)
+    where
+        M: FnMut(&T) -> bool,
+        B: FnMut(&T) -> bool,
+    {
+        let (width, height) = (self.width(), self.height());
+        let (lines, len) = if heading.is_vertical() {
+            (width, height)
+        } else {
+            (height, width)
+        };
+
+        for line in 0..lines {
+            // the next free spot, counted from the edge everything slides toward
+            let mut free = 0;
+            for k in 0..len {
+                let position = tilt_position(heading, line, k, len);
+                let value = &self.0[position.0][position.1];
+                if blocker(value) {
+                    free = k + 1;
+                } else if movable(value) {
+                    if free != k {
+                        swap_cells(
+                            &mut self.0,
+                            tilt_position(heading, line, free, len),
+                            position,
+                        );
+                    }
+                    free += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The `(row, column)` of the `k`th cell of `line` counted from the edge `heading` points at.
+fn tilt_position(heading: Cardinal, line: usize, k: usize, len: usize) -> (usize, usize) {
+    match heading {
+        Cardinal::Up => (k, line),
+        Cardinal::Down => (len - 1 - k, line),
+        Cardinal::Left => (line, k),
+        Cardinal::Right => (line, len - 1 - k),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use anyhow::Result;
+
+    fn rows(grid: &Grid<char>) -> Vec<String> {
+        grid.iter().map(|row| row.iter().collect()).collect()
+    }
+
+    fn tilt(grid: &mut Grid<char>, heading: Cardinal) {
+        grid.tilt(heading, |&c| c == 'O', |&c| c == '#');
+    }
+
+    const PLATFORM: [&str; 10] = [
+        "O....#....",
+        "O.OO#....#",
+        ".....##...",
+        "OO.#O....O",
+        ".O.....O#.",
+        "O.#..O.#.#",
+        "..O..#O..O",
+        ".......O..",
+        "#....###..",
+        "#OO..#....",
+    ];
+
+    #[test]
+    fn test_tilt_north_load() -> Result<()> {
+        let mut grid = Grid::from_lines(&PLATFORM, YAxis::Down, |c| c)?;
+        tilt(&mut grid, Cardinal::Up);
+        let load: usize = grid
+            .iter()
+            .enumerate()
+            .map(|(y, row)| row.iter().filter(|&&c| c == 'O').count() * (grid.height() - y))
+            .sum();
+        assert_eq!(load, 136);
+        assert_eq!(rows(&grid)[0], "OOOO.#.O..");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tilt_spin_cycle() -> Result<()> {
+        let mut grid = Grid::from_lines(&PLATFORM, YAxis::Down, |c| c)?;
+        for heading in [
+            Cardinal::Up,
+            Cardinal::Left,
+            Cardinal::Down,
+            Cardinal::Right,
+        ] {
+            tilt(&mut grid, heading);
+        }
+        assert_eq!(
+            rows(&grid),
+            [
+                ".....#....",
+                "....#...O#",
+                "...OO##...",
+                ".OO#......",
+                ".....OOO#.",
+                ".O#...O#.#",
+                "....O#....",
+                "......OOOO",
+                "#...O###..",
+                "#..OO#....",
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use super::Cardinal;
+use crate::math::two_dimensional::IPoint;
+use std::collections::HashSet;
+
+/// A walker on the unbounded integer plane that records where it has been, both in order and as a set, and remembers
+/// the first point it stepped onto twice. `y` grows downward, matching [`Cardinal::delta`].
+#[derive(Debug, Clone)]
+pub struct Turtle {
+    position: IPoint,
+    heading: Cardinal,
+    path: Vec<IPoint>,
+    visited: HashSet<IPoint>,
+    first_revisit: Option<IPoint>,
+}
+
+impl Turtle {
+    pub fn new(start: IPoint, heading: Cardinal) -> Self {
+        Self {
+            position: start,
+            heading,
+            path: vec![start],
+            visited: HashSet::from([start]),
+            first_revisit: None,
+        }
+    }
+
+    pub fn position(&self) -> IPoint {
+        self.position
+    }
+
+    pub fn heading(&self) -> Cardinal {
+        self.heading
+    }
+
+    pub fn face(&mut self, heading: Cardinal) {
+        self.heading = heading;
+    }
+
+    pub fn turn_left(&mut self) {
+        self.heading = self.heading.turn_left();
+    }
+
+    pub fn turn_right(&mut self) {
+        self.heading = self.heading.turn_right();
+    }
+
+    /// Moves `steps` points along the current heading, recording each one.
+    pub fn forward(&mut self, steps: usize) {
+        let (dx, dy) = self.heading.delta();
+        let delta = IPoint::new(dx as i64, dy as i64);
+        for _ in 0..steps {
+            self.position = self.position + delta;
+            self.path.push(self.position);
+            if !self.visited.insert(self.position) && self.first_revisit.is_none() {
+                self.first_revisit = Some(self.position);
+            }
+        }
+    }
+
+    /// Faces `heading`, then moves `steps` points.
+    pub fn walk(&mut self, heading: Cardinal, steps: usize) {
+        self.face(heading);
+        self.forward(steps);
+    }
+
+    /// Every point visited, in order, starting with the start point. Revisited points appear again.
+    pub fn path(&self) -> &[IPoint] {
+        &self.path
+    }
+
+    /// The distinct points visited, including the start.
+    pub fn visited(&self) -> &HashSet<IPoint> {
+        &self.visited
+    }
+
+    /// The first point stepped onto a second time, if any.
+    pub fn first_revisit(&self) -> Option<IPoint> {
+        self.first_revisit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turtle() {
+        // 2016 day 1, "R8, R4, R4, R8"
+        let mut turtle = Turtle::new(IPoint::new(0, 0), Cardinal::Up);
+        for steps in [8, 4, 4, 8] {
+            turtle.turn_right();
+            turtle.forward(steps);
+        }
+
+        let origin = IPoint::new(0, 0);
+        assert_eq!(turtle.first_revisit(), Some(IPoint::new(4, 0)));
+        assert_eq!(
+            turtle.first_revisit().unwrap().manhattan_distance(&origin),
+            4
+        );
+        assert_eq!(turtle.position(), IPoint::new(4, -4));
+        assert_eq!(turtle.heading(), Cardinal::Up);
+        assert_eq!(turtle.path().len(), 25);
+        assert_eq!(turtle.visited().len(), 24);
+    }
+
+    #[test]
+    fn test_turtle_walk() {
+        let mut turtle = Turtle::new(IPoint::new(0, 0), Cardinal::Right);
+        turtle.walk(Cardinal::Left, 2);
+        turtle.walk(Cardinal::Down, 1);
+        assert_eq!(
+            turtle.path(),
+            [
+                IPoint::new(0, 0),
+                IPoint::new(-1, 0),
+                IPoint::new(-2, 0),
+                IPoint::new(-2, 1)
+            ]
+        );
+        assert_eq!(turtle.first_revisit(), None);
+    }
+}
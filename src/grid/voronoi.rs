@@ -0,0 +1,142 @@
+use super::Grid;
+use crate::math::two_dimensional::Point;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The closest source to a cell of a [`Voronoi`] diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nearest {
+    /// The index of the one source closer than any other
+    Source(usize),
+    /// Two or more sources are equally close
+    Tie,
+}
+
+/// Every cell of a `width` by `height` grid labeled with its nearest source by Manhattan distance.
+///
+/// A source's region is infinite when it reaches the edge of the grid: past the edge, the same source stays closest
+/// forever. Size the grid to cover every source, as [`covering`](Self::covering) does, for that to hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voronoi {
+    cells: Grid<Nearest>,
+    sources: usize,
+}
+
+impl Voronoi {
+    pub fn new(width: usize, height: usize, sources: &[Point]) -> Self {
+        let cells = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| nearest(Point::new(x, y), sources))
+                    .collect()
+            })
+            .collect();
+        Self {
+            cells: Grid(cells),
+            sources: sources.len(),
+        }
+    }
+
+    /// The diagram over the smallest grid anchored at the origin that holds every source.
+    pub fn covering(sources: &[Point]) -> Self {
+        let width = sources.iter().map(|p| p.x + 1).max().unwrap_or(0);
+        let height = sources.iter().map(|p| p.y + 1).max().unwrap_or(0);
+        Self::new(width, height, sources)
+    }
+
+    pub fn cells(&self) -> &Grid<Nearest> {
+        &self.cells
+    }
+
+    /// The number of cells nearest to each source, by source index.
+    pub fn areas(&self) -> Vec<usize> {
+        let mut areas = vec![0; self.sources];
+        for cell in self.cells.iter().flatten() {
+            if let Nearest::Source(i) = cell {
+                areas[*i] += 1;
+            }
+        }
+        areas
+    }
+
+    /// Whether each source's region, by source index, stays inside the grid.
+    pub fn finite(&self) -> Vec<bool> {
+        let mut finite = vec![true; self.sources];
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let edge = (0..width)
+            .flat_map(|x| [Point::new(x, 0), Point::new(x, height.saturating_sub(1))])
+            .chain(
+                (0..height)
+                    .flat_map(|y| [Point::new(0, y), Point::new(width.saturating_sub(1), y)]),
+            );
+        for position in edge {
+            if let Some(Nearest::Source(i)) = self.cells.get(position) {
+                finite[*i] = false;
+            }
+        }
+        finite
+    }
+
+    /// The area of each source's region, by source index, or `None` for infinite ones.
+    pub fn finite_areas(&self) -> Vec<Option<usize>> {
+        self.areas()
+            .into_iter()
+            .zip(self.finite())
+            .map(|(area, finite)| finite.then_some(area))
+            .collect()
+    }
+}
+
+fn nearest(position: Point, sources: &[Point]) -> Nearest {
+    let mut best = None;
+    let mut tied = false;
+    for (i, source) in sources.iter().enumerate() {
+        let distance = position.manhattan_distance(source);
+        match best {
+            Some((_, d)) if distance > d => (),
+            Some((_, d)) if distance == d => tied = true,
+            _ => {
+                best = Some((i, distance));
+                tied = false;
+            }
+        }
+    }
+    match best {
+        Some((i, _)) if !tied => Nearest::Source(i),
+        _ => Nearest::Tie,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voronoi_chronal_coordinates() {
+        let sources =
+            [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)].map(|(x, y)| Point::new(x, y));
+        let voronoi = Voronoi::covering(&sources);
+
+        assert_eq!(voronoi.cells()[Point::new(0, 0)], Nearest::Source(0));
+        assert_eq!(voronoi.cells()[Point::new(0, 4)], Nearest::Tie);
+        assert_eq!(voronoi.finite(), [false, false, false, true, true, false]);
+        assert_eq!(
+            voronoi.finite_areas(),
+            [None, None, None, Some(9), Some(17), None]
+        );
+        let ties = voronoi
+            .cells()
+            .iter()
+            .flatten()
+            .filter(|&&c| c == Nearest::Tie)
+            .count();
+        assert_eq!(voronoi.areas().iter().sum::<usize>() + ties, 9 * 10);
+    }
+
+    #[test]
+    fn test_voronoi_empty() {
+        let voronoi = Voronoi::new(2, 2, &[]);
+        assert!(voronoi.cells().iter().flatten().all(|&c| c == Nearest::Tie));
+        assert!(voronoi.finite_areas().is_empty());
+    }
+}
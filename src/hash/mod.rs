@@ -0,0 +1,87 @@
+pub use crate::parse::to_hex;
+use crate::search::par_find_first;
+use md5::{Digest, Md5};
+
+pub fn md5(s: impl AsRef<[u8]>) -> [u8; 16] {
+    Md5::digest(s).into()
+}
+
+pub fn md5_hex(s: impl AsRef<[u8]>) -> String {
+    to_hex(&md5(s))
+}
+
+fn has_leading_zeros(digest: &[u8; 16], zeros: usize) -> bool {
+    digest[..zeros / 2].iter().all(|&b| b == 0)
+        && (zeros.is_multiple_of(2) || digest[zeros / 2] >> 4 == 0)
+}
+
+fn hash_nonce(hasher: &Md5, nonce: u64) -> [u8; 16] {
+    let mut buf = [0; 20];
+    let mut start = buf.len();
+    let mut n = nonce;
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+
+    let mut hasher = hasher.clone();
+    hasher.update(&buf[start..]);
+    hasher.finalize().into()
+}
+
+/// Every nonce, in increasing order, for which the MD5 of `prefix` followed by the nonce in decimal starts with
+/// `leading_zeros` hex zeros, along with its digest. Each one is found with [`par_find_first`], searching on from the
+/// one before.
+pub fn nonces(prefix: &str, leading_zeros: usize) -> impl Iterator<Item = (u64, [u8; 16])> {
+    assert!(leading_zeros <= 32, "an MD5 digest has only 32 hex digits");
+    let hasher = Md5::new_with_prefix(prefix);
+
+    let mut next = 0;
+    std::iter::from_fn(move || {
+        let nonce = par_find_first(next..u64::MAX, |nonce| {
+            has_leading_zeros(&hash_nonce(&hasher, nonce), leading_zeros)
+        })?;
+        next = nonce + 1;
+        Some((nonce, hash_nonce(&hasher, nonce)))
+    })
+}
+
+/// The lowest nonce for which the MD5 of `prefix` followed by the nonce starts with `leading_zeros` hex zeros.
+pub fn find_nonce(prefix: &str, leading_zeros: usize) -> u64 {
+    nonces(prefix, leading_zeros)
+        .next()
+        .map(|(nonce, _)| nonce)
+        .expect("ran out of nonces")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_md5_hex() {
+        assert_eq!(md5_hex(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_hex("abcdef609043"), "000001dbbfa3a5c83a2d506429c7b00e");
+    }
+
+    #[test]
+    fn test_find_nonce() {
+        assert_eq!(find_nonce("abcdef", 5), 609043);
+        assert_eq!(find_nonce("abc", 0), 0);
+    }
+
+    #[test]
+    fn test_nonces() {
+        let expected: Vec<_> = (0..)
+            .map(|n| (n, md5(format!("abc{n}"))))
+            .filter(|(_, digest)| to_hex(digest).starts_with("000"))
+            .take(5)
+            .collect();
+
+        assert_eq!(nonces("abc", 3).take(5).collect::<Vec<_>>(), expected);
+    }
+}
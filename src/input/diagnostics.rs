@@ -0,0 +1,132 @@
+/// Collects formatting problems seen while reading an input — the kind that pasted inputs pick up silently and that
+/// are otherwise only noticed when a parser misbehaves.
+#[derive(Debug, Default)]
+pub(super) struct Diagnostics {
+    crlf: Vec<usize>,
+    trailing_whitespace: Vec<usize>,
+    non_ascii: Vec<usize>,
+    lengths: Vec<(usize, usize)>,
+    grid_like: bool,
+}
+
+impl Diagnostics {
+    pub(super) fn new() -> Self {
+        Self {
+            grid_like: true,
+            ..Default::default()
+        }
+    }
+
+    /// Checks one raw line, numbered from 1, before any normalization.
+    pub(super) fn check_line(&mut self, number: usize, line: &str) {
+        let content = line.strip_suffix('\r').unwrap_or(line);
+        if content.len() != line.len() {
+            self.crlf.push(number);
+        }
+        if content.trim_end().len() != content.len() {
+            self.trailing_whitespace.push(number);
+        }
+        if !line.is_ascii() {
+            self.non_ascii.push(number);
+        }
+
+        let content = content.trim_end();
+        if !content.is_empty() {
+            self.grid_like &= !content.contains(char::is_whitespace);
+            self.lengths.push((number, content.chars().count()));
+        }
+    }
+
+    /// Describes each problem found, naming the first few affected lines.
+    pub(super) fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut warn = |lines: &[usize], problem: &str| {
+            if !lines.is_empty() {
+                warnings.push(format!(
+                    "{} {problem}: {}",
+                    plural(lines.len()),
+                    sample(lines)
+                ));
+            }
+        };
+        warn(&self.crlf, "with CRLF line endings");
+        warn(&self.trailing_whitespace, "with trailing whitespace");
+        warn(&self.non_ascii, "with non-ASCII characters");
+
+        // Only grid-looking inputs — several lines without inner whitespace — are expected to be rectangular
+        if self.grid_like
+            && let [(_, width), ..] = self.lengths[..]
+            && self.lengths.len() > 1
+        {
+            let ragged = self
+                .lengths
+                .iter()
+                .filter(|&&(_, len)| len != width)
+                .map(|&(n, _)| n)
+                .collect::<Vec<_>>();
+            warn(
+                &ragged,
+                &format!("whose length differs from line 1's {width}"),
+            );
+        }
+        warnings
+    }
+}
+
+fn plural(count: usize) -> String {
+    match count {
+        1 => "1 line".to_string(),
+        n => format!("{n} lines"),
+    }
+}
+
+fn sample(lines: &[usize]) -> String {
+    const SHOWN: usize = 5;
+    let shown = lines[..lines.len().min(SHOWN)]
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if lines.len() > SHOWN {
+        format!("{shown}, ...")
+    } else {
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warnings(input: &str) -> Vec<String> {
+        let mut diagnostics = Diagnostics::new();
+        for (i, line) in input.split('\n').enumerate() {
+            diagnostics.check_line(i + 1, line);
+        }
+        diagnostics.warnings()
+    }
+
+    #[test]
+    fn test_clean_input() {
+        assert!(warnings("#.#\n..#\n###").is_empty());
+        assert!(warnings("move 1 from 2 to 3\nmove 10 from 2 to 3").is_empty());
+    }
+
+    #[test]
+    fn test_warnings() {
+        assert_eq!(
+            warnings("#.#\r\n..# \n#é#\n##\n\n#.#"),
+            [
+                "1 line with CRLF line endings: 1",
+                "1 line with trailing whitespace: 2",
+                "1 line with non-ASCII characters: 3",
+                "1 line whose length differs from line 1's 3: 4",
+            ]
+        );
+        let crlf = "a\r\n".repeat(7);
+        assert_eq!(
+            warnings(&crlf),
+            ["7 lines with CRLF line endings: 1, 2, 3, 4, 5, ..."]
+        );
+    }
+}
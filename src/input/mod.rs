@@ -0,0 +1,374 @@
+mod diagnostics;
+
+use crate::error::{Error, Result};
+use diagnostics::Diagnostics;
+#[cfg(feature = "compression")]
+use flate2::read::MultiGzDecoder;
+use log::{debug, trace};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "compression")]
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "zst"];
+#[cfg(not(feature = "compression"))]
+const COMPRESSED_EXTENSIONS: &[&str] = &[];
+const EXAMPLE_PREFIX: &str = "example";
+const ANSWERS_SUFFIX: &str = ".answers";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// strip stray `\r` characters left at the end of each line
+    pub strip_cr: bool,
+    /// trim trailing whitespace from each line
+    pub trim_trailing_whitespace: bool,
+    /// drop blank lines at the end of the input
+    pub drop_trailing_blank_lines: bool,
+    /// log, at debug level, formatting problems such as CRLF line endings, trailing whitespace, non-ASCII characters,
+    /// and ragged lines in grid-looking input
+    pub diagnose: bool,
+}
+
+impl ReadOptions {
+    pub fn normalized() -> Self {
+        Self {
+            strip_cr: true,
+            trim_trailing_whitespace: true,
+            drop_trailing_blank_lines: true,
+            diagnose: false,
+        }
+    }
+
+    fn apply_line(&self, mut line: String) -> String {
+        if self.trim_trailing_whitespace {
+            line.truncate(line.trim_end().len());
+        } else if self.strip_cr {
+            line.truncate(line.trim_end_matches('\r').len());
+        }
+        line
+    }
+
+    fn apply_end(&self, lines: &mut Vec<String>) {
+        if self.drop_trailing_blank_lines {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+        }
+    }
+}
+
+/// Where puzzle inputs come from, so solutions aren't tied to the filesystem.
+pub trait InputSource: Debug {
+    /// Opens the input with the given name, e.g. `input` or `example`.
+    fn open(&self, name: &str) -> Result<Box<dyn Read + '_>>;
+
+    /// Names of the available inputs, for sources that can list them.
+    fn names(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Inputs stored as files in a directory. With the `compression` feature, a missing file falls back to a `.gz` or
+/// `.zst` compressed sibling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectorySource {
+    dir: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl InputSource for DirectorySource {
+    fn open(&self, name: &str) -> Result<Box<dyn Read + '_>> {
+        open_input(&self.dir.join(name))
+    }
+
+    fn names(&self) -> Result<Vec<String>> {
+        let entries = std::fs::read_dir(&self.dir).map_err(io_error(&self.dir))?;
+        let mut names = Vec::new();
+        for entry in entries {
+            let name = entry.map_err(io_error(&self.dir))?.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            let name = COMPRESSED_EXTENSIONS
+                .iter()
+                .find_map(|ext| name.strip_suffix(ext)?.strip_suffix('.'))
+                .unwrap_or(name);
+            names.push(name.to_string());
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+/// Inputs held in memory, for tests, embedded examples, or targets without a filesystem.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemorySource {
+    inputs: HashMap<String, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.inputs.insert(name.into(), contents.into());
+        self
+    }
+}
+
+impl InputSource for MemorySource {
+    fn open(&self, name: &str) -> Result<Box<dyn Read + '_>> {
+        let contents = self
+            .inputs
+            .get(name)
+            .ok_or_else(|| io_error(name)(ErrorKind::NotFound.into()))?;
+        Ok(Box::new(contents.as_bytes()))
+    }
+
+    fn names(&self) -> Result<Vec<String>> {
+        let mut names = self.inputs.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Opens `path`, falling back to a compressed sibling if it doesn't exist and the `compression` feature is enabled.
+fn open_input(path: &Path) -> Result<Box<dyn Read>> {
+    let path = if path.exists() {
+        path.to_path_buf()
+    } else {
+        COMPRESSED_EXTENSIONS
+            .iter()
+            .map(|ext| with_appended_extension(path, ext))
+            .find(|p| p.exists())
+            .ok_or_else(|| io_error(path)(ErrorKind::NotFound.into()))?
+    };
+
+    let file = File::open(&path).map_err(io_error(&path))?;
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "compression")]
+        Some("gz") => Box::new(MultiGzDecoder::new(file)),
+        #[cfg(feature = "compression")]
+        Some("zst") => Box::new(zstd::Decoder::new(file).map_err(io_error(&path))?),
+        _ => Box::new(file),
+    })
+}
+
+fn io_error(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Error {
+    let path = path.into();
+    |source| Error::Io { path, source }
+}
+
+fn with_appended_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+    PathBuf::from(path)
+}
+
+pub fn read_lines(path: impl AsRef<Path>, options: ReadOptions) -> Result<Vec<String>> {
+    let path = path.as_ref();
+    lines_from_reader(BufReader::new(open_input(path)?), options).map_err(io_error(path))
+}
+
+/// Reads the named input from `source`.
+pub fn read_input(
+    source: &dyn InputSource,
+    name: &str,
+    options: ReadOptions,
+) -> Result<Vec<String>> {
+    lines_from_reader(BufReader::new(source.open(name)?), options).map_err(io_error(name))
+}
+
+/// Names of the example inputs in `source` — `example`, `example2`, `example-part2` and so on — in natural order,
+/// excluding their `.answers` files.
+pub fn example_names(source: &dyn InputSource) -> Result<Vec<String>> {
+    let mut names = source
+        .names()?
+        .into_iter()
+        .filter(|name| name.starts_with(EXAMPLE_PREFIX) && !name.ends_with(ANSWERS_SUFFIX))
+        .collect::<Vec<_>>();
+    names.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+    Ok(names)
+}
+
+/// Reads the expected answers for the named example from its `<name>.answers` file, one line per part, with blank
+/// lines for unknown answers. A missing file means no answers are known.
+pub fn read_expected_answers(source: &dyn InputSource, name: &str) -> Result<Vec<Option<String>>> {
+    let name = format!("{name}{ANSWERS_SUFFIX}");
+    match read_input(source, &name, ReadOptions::normalized()) {
+        Ok(lines) => Ok(lines
+            .into_iter()
+            .map(|line| Some(line.trim().to_string()).filter(|l| !l.is_empty()))
+            .collect()),
+        Err(Error::Io { source, .. }) if source.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn lines_from_reader(reader: impl BufRead, options: ReadOptions) -> io::Result<Vec<String>> {
+    let (lines, warnings) = diagnosed_lines(reader, options)?;
+    for warning in warnings {
+        debug!("Input has {warning}");
+    }
+    Ok(lines)
+}
+
+/// Reads lines as [`BufRead::lines`] would, along with any diagnostics warnings. Lines are read raw so the diagnostics
+/// still see the `\r` that `lines()` strips from each `\r\n`.
+fn diagnosed_lines(
+    mut reader: impl BufRead,
+    options: ReadOptions,
+) -> io::Result<(Vec<String>, Vec<String>)> {
+    let mut lines = Vec::new();
+    let mut diagnostics = options.diagnose.then(Diagnostics::new);
+    let mut raw = String::new();
+    while reader.read_line(&mut raw)? > 0 {
+        let mut line = std::mem::take(&mut raw);
+        let terminated = line.ends_with('\n');
+        if terminated {
+            line.pop();
+        }
+        if let Some(diagnostics) = &mut diagnostics {
+            diagnostics.check_line(lines.len() + 1, &line);
+        }
+        if terminated && line.ends_with('\r') {
+            line.pop();
+        }
+        let line = options.apply_line(line);
+        trace!("{}", line);
+        lines.push(line);
+    }
+    let warnings = diagnostics.iter().flat_map(Diagnostics::warnings).collect();
+    options.apply_end(&mut lines);
+    Ok((lines, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    const RAW: &str = "ab\r\ncd  \r\nef\r\r\n\t\r\n\r\n  \n";
+
+    #[test]
+    fn test_read_options_default() -> Result<()> {
+        let lines = lines_from_reader(RAW.as_bytes(), ReadOptions::default())?;
+        assert_eq!(lines, vec!["ab", "cd  ", "ef\r", "\t", "", "  "]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_options_strip_cr() -> Result<()> {
+        let options = ReadOptions {
+            strip_cr: true,
+            ..Default::default()
+        };
+        let lines = lines_from_reader(RAW.as_bytes(), options)?;
+        assert_eq!(lines, vec!["ab", "cd  ", "ef", "\t", "", "  "]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_options_normalized() -> Result<()> {
+        let lines = lines_from_reader(RAW.as_bytes(), ReadOptions::normalized())?;
+        assert_eq!(lines, vec!["ab", "cd", "ef"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagnose_crlf() -> Result<()> {
+        let options = ReadOptions {
+            diagnose: true,
+            ..ReadOptions::normalized()
+        };
+        let (lines, warnings) = diagnosed_lines("a\r\nb\r\n".as_bytes(), options)?;
+        assert_eq!(lines, ["a", "b"]);
+        assert_eq!(warnings, ["2 lines with CRLF line endings: 1, 2"]);
+
+        let source = MemorySource::new().with("input", "a\r\nb\r\n");
+        assert_eq!(read_input(&source, "input", options)?, ["a", "b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_source() -> Result<()> {
+        let source = MemorySource::new().with("example", RAW);
+        let lines = read_input(&source, "example", ReadOptions::normalized())?;
+        assert_eq!(lines, vec!["ab", "cd", "ef"]);
+
+        let err = read_input(&source, "input", ReadOptions::default()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Io { ref path, ref source }
+                if path == Path::new("input") && source.kind() == ErrorKind::NotFound
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_directory_source() -> Result<()> {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("aoc_util-compressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+
+        let mut gz = GzEncoder::new(File::create(dir.join("input.gz"))?, Compression::default());
+        gz.write_all(RAW.as_bytes())?;
+        gz.finish()?;
+        std::fs::write(
+            dir.join("example.zst"),
+            zstd::encode_all(RAW.as_bytes(), 0)?,
+        )?;
+        std::fs::write(dir.join("example2"), "plain\n")?;
+
+        let source = DirectorySource::new(&dir);
+        let read = |name| read_input(&source, name, ReadOptions::normalized());
+        let result = (|| -> Result<()> {
+            assert_eq!(read("input")?, ["ab", "cd", "ef"]);
+            assert_eq!(read("example")?, ["ab", "cd", "ef"]);
+            assert_eq!(read("example2")?, ["plain"]);
+            assert_eq!(source.names()?, ["example", "example2", "input"]);
+            assert!(read("example3").is_err());
+            Ok(())
+        })();
+        std::fs::remove_dir_all(&dir)?;
+        result
+    }
+
+    #[test]
+    fn test_examples() -> Result<()> {
+        let source = MemorySource::new()
+            .with("input", "1")
+            .with("example10", "10")
+            .with("example2", "2")
+            .with("example", "1")
+            .with("example.answers", "142\n\n")
+            .with("example2.answers", "\n281\n");
+        assert_eq!(
+            example_names(&source)?,
+            ["example", "example2", "example10"]
+        );
+        assert_eq!(
+            read_expected_answers(&source, "example")?,
+            [Some("142".to_string())]
+        );
+        assert_eq!(
+            read_expected_answers(&source, "example2")?,
+            [None, Some("281".to_string())]
+        );
+        assert!(read_expected_answers(&source, "example10")?.is_empty());
+        Ok(())
+    }
+}
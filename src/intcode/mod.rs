@@ -0,0 +1,370 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::thread::{self, JoinHandle};
+
+use anyhow::{Result, anyhow, bail};
+
+/// Why [`Intcode::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// The program executed opcode 99
+    Halted,
+    /// The program tried to read from an empty input queue; push more input and run again
+    NeedsInput,
+}
+
+/// An Intcode computer, per the full 2019 spec: position, immediate and relative parameter modes, a relative base,
+/// and memory that grows on demand.
+///
+/// I/O goes through queues: [`Intcode::run`] returns [`State::NeedsInput`] rather than blocking when input runs out.
+/// For blocking I/O over channels, see [`Intcode::spawn`].
+#[derive(Debug, Clone)]
+pub struct Intcode {
+    memory: Vec<i64>,
+    ip: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    halted: bool,
+}
+
+/// Parses a comma-separated Intcode program.
+pub fn parse(s: &str) -> Result<Vec<i64>> {
+    s.trim()
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .parse()
+                .map_err(|e| anyhow!("Invalid Intcode value {n:?}: {e}"))
+        })
+        .collect()
+}
+
+impl FromStr for Intcode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self::new(parse(s)?))
+    }
+}
+
+impl Intcode {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            memory: program,
+            ip: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            halted: false,
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// The full memory, including any addresses grown into.
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    /// Reads `address`; unwritten memory beyond the program reads as 0.
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn write(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Queues each byte of `s` as input, for the ASCII-driven puzzles.
+    pub fn push_ascii(&mut self, s: &str) {
+        self.input.extend(s.bytes().map(i64::from));
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+
+    pub fn drain_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    fn address(&self, offset: usize) -> Result<usize> {
+        let instruction = self.read(self.ip);
+        let mode = instruction / 10i64.pow(offset as u32 + 1) % 10;
+        let raw = self.read(self.ip + offset);
+        let address = match mode {
+            0 => raw,
+            1 => return Ok(self.ip + offset),
+            2 => self.relative_base + raw,
+            _ => bail!(
+                "Invalid parameter mode {mode} in {instruction} at {}",
+                self.ip
+            ),
+        };
+        usize::try_from(address).map_err(|_| anyhow!("Negative address {address} at {}", self.ip))
+    }
+
+    fn param(&self, offset: usize) -> Result<i64> {
+        Ok(self.read(self.address(offset)?))
+    }
+
+    fn write_param(&mut self, offset: usize, value: i64) -> Result<()> {
+        if self.read(self.ip) / 10i64.pow(offset as u32 + 1) % 10 == 1 {
+            bail!("Write parameter in immediate mode at {}", self.ip);
+        }
+        let address = self.address(offset)?;
+        self.write(address, value);
+        Ok(())
+    }
+
+    /// Executes a single instruction. Returns `Some` if the program halted or is waiting for input, in which case the
+    /// instruction pointer has not moved.
+    pub fn step(&mut self) -> Result<Option<State>> {
+        if self.halted {
+            return Ok(Some(State::Halted));
+        }
+
+        let opcode = self.read(self.ip) % 100;
+        self.ip = match opcode {
+            1 | 2 | 7 | 8 => {
+                let (a, b) = (self.param(1)?, self.param(2)?);
+                let value = match opcode {
+                    1 => a + b,
+                    2 => a * b,
+                    7 => i64::from(a < b),
+                    _ => i64::from(a == b),
+                };
+                self.write_param(3, value)?;
+                self.ip + 4
+            }
+            3 => {
+                let Some(value) = self.input.pop_front() else {
+                    return Ok(Some(State::NeedsInput));
+                };
+                self.write_param(1, value)?;
+                self.ip + 2
+            }
+            4 => {
+                let value = self.param(1)?;
+                self.output.push_back(value);
+                self.ip + 2
+            }
+            5 | 6 => {
+                if (self.param(1)? != 0) == (opcode == 5) {
+                    usize::try_from(self.param(2)?)
+                        .map_err(|_| anyhow!("Negative jump target at {}", self.ip))?
+                } else {
+                    self.ip + 3
+                }
+            }
+            9 => {
+                self.relative_base += self.param(1)?;
+                self.ip + 2
+            }
+            99 => {
+                self.halted = true;
+                return Ok(Some(State::Halted));
+            }
+            _ => bail!("Invalid opcode {} at {}", self.read(self.ip), self.ip),
+        };
+        Ok(None)
+    }
+
+    /// Runs until the program halts or needs input that hasn't been queued.
+    pub fn run(&mut self) -> Result<State> {
+        loop {
+            if let Some(state) = self.step()? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Runs until the program produces an output, returning it, or stops without one.
+    pub fn run_until_output(&mut self) -> Result<Option<i64>> {
+        loop {
+            if let Some(value) = self.output.pop_front() {
+                return Ok(Some(value));
+            }
+            if self.step()?.is_some() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Runs to completion with `input`, returning everything it output.
+    pub fn run_with(&mut self, input: impl IntoIterator<Item = i64>) -> Result<Vec<i64>> {
+        self.input.extend(input);
+        match self.run()? {
+            State::Halted => Ok(self.drain_output()),
+            State::NeedsInput => bail!("Program needs more input"),
+        }
+    }
+
+    /// Runs on its own thread with blocking I/O: reads block on `input` and each output is sent as it is produced.
+    /// The thread ends when the program halts, or fails if the program needs input after `input` is disconnected.
+    pub fn run_on(mut self, input: Receiver<i64>, output: Sender<i64>) -> JoinHandle<Result<Self>> {
+        thread::spawn(move || {
+            loop {
+                let state = self.step()?;
+                for value in self.output.drain(..) {
+                    // a disconnected receiver just means nobody cares about the rest of the output
+                    let _ = output.send(value);
+                }
+                match state {
+                    Some(State::Halted) => return Ok(self),
+                    Some(State::NeedsInput) => {
+                        let value = input
+                            .recv()
+                            .map_err(|_| anyhow!("Input channel closed while waiting for input"))?;
+                        self.push_input(value);
+                    }
+                    None => {}
+                }
+            }
+        })
+    }
+
+    /// Like [`Intcode::run_on`], creating the channels.
+    pub fn spawn(self) -> (Sender<i64>, Receiver<i64>, JoinHandle<Result<Self>>) {
+        let (input_tx, input_rx) = channel();
+        let (output_tx, output_rx) = channel();
+        (input_tx, output_rx, self.run_on(input_rx, output_tx))
+    }
+}
+
+/// Runs machines wired in a chain, each machine's output feeding the next one's input, with `input` sent to the first.
+/// With `feedback`, the last machine's output loops back to the first. Runs until every machine halts or none can make
+/// progress, and returns the last value the final machine output.
+pub fn run_chain(machines: &mut [Intcode], input: i64, feedback: bool) -> Result<Option<i64>> {
+    let n = machines.len();
+    let Some(first) = machines.first_mut() else {
+        return Ok(None);
+    };
+    first.push_input(input);
+
+    let mut last = None;
+    loop {
+        let mut progressed = false;
+        for i in 0..n {
+            machines[i].run()?;
+            let values = machines[i].drain_output();
+            progressed |= !values.is_empty();
+
+            if i == n - 1 {
+                last = values.last().copied().or(last);
+            }
+            let next = match (i + 1 == n, feedback) {
+                (false, _) => i + 1,
+                (true, true) => 0,
+                (true, false) => continue,
+            };
+            machines[next].input.extend(values);
+        }
+
+        if !progressed || machines.iter().all(Intcode::is_halted) {
+            return Ok(last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amplify(program: &str, phases: &[i64], feedback: bool) -> i64 {
+        let mut machines: Vec<Intcode> = phases
+            .iter()
+            .map(|&phase| {
+                let mut machine: Intcode = program.parse().unwrap();
+                machine.push_input(phase);
+                machine
+            })
+            .collect();
+        run_chain(&mut machines, 0, feedback).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_intcode_memory() {
+        let mut machine: Intcode = "1,9,10,3,2,3,11,0,99,30,40,50".parse().unwrap();
+        assert_eq!(machine.run().unwrap(), State::Halted);
+        assert_eq!(machine.read(0), 3500);
+        assert_eq!(machine.read(1000), 0);
+
+        let mut machine: Intcode = "1002,4,3,4,33".parse().unwrap();
+        machine.run().unwrap();
+        assert_eq!(machine.memory(), [1002, 4, 3, 4, 99]);
+    }
+
+    #[test]
+    fn test_intcode_io() {
+        let program = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,\
+                       1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+        for (input, expected) in [(7, 999), (8, 1000), (9, 1001)] {
+            let mut machine: Intcode = program.parse().unwrap();
+            assert_eq!(machine.run_with([input]).unwrap(), [expected]);
+        }
+
+        let mut machine: Intcode = "3,9,8,9,10,9,4,9,99,-1,8".parse().unwrap();
+        assert_eq!(machine.run().unwrap(), State::NeedsInput);
+        machine.push_input(8);
+        assert_eq!(machine.run_until_output().unwrap(), Some(1));
+        assert_eq!(machine.run_until_output().unwrap(), None);
+        assert!(machine.is_halted());
+    }
+
+    #[test]
+    fn test_intcode_relative_base() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let mut machine: Intcode = quine.parse().unwrap();
+        assert_eq!(machine.run_with([]).unwrap(), parse(quine).unwrap());
+
+        let mut machine: Intcode = "104,1125899906842624,99".parse().unwrap();
+        assert_eq!(machine.run_with([]).unwrap(), [1125899906842624]);
+
+        let mut machine: Intcode = "1102,34915192,34915192,7,4,7,99,0".parse().unwrap();
+        assert_eq!(machine.run_with([]).unwrap()[0].to_string().len(), 16);
+    }
+
+    #[test]
+    fn test_intcode_errors() {
+        assert!("1,2,x".parse::<Intcode>().is_err());
+        assert!("42".parse::<Intcode>().unwrap().run().is_err());
+        assert!("3,0,99".parse::<Intcode>().unwrap().run_with([]).is_err());
+        assert!("11101,1,1,0,99".parse::<Intcode>().unwrap().run().is_err());
+    }
+
+    #[test]
+    fn test_intcode_chain() {
+        let program = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
+        assert_eq!(amplify(program, &[4, 3, 2, 1, 0], false), 43210);
+
+        let program =
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+        assert_eq!(amplify(program, &[9, 8, 7, 6, 5], true), 139629729);
+    }
+
+    #[test]
+    fn test_intcode_spawn() {
+        let machine: Intcode = "3,9,8,9,10,9,4,9,99,-1,8".parse().unwrap();
+        let (input, output, handle) = machine.spawn();
+        input.send(8).unwrap();
+        assert_eq!(output.recv().unwrap(), 1);
+        assert!(handle.join().unwrap().unwrap().is_halted());
+
+        let machine: Intcode = "3,0,99".parse().unwrap();
+        let (input, _output, handle) = machine.spawn();
+        drop(input);
+        assert!(handle.join().unwrap().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::client;
+use crate::config;
+use anyhow::{Result, anyhow};
+use log::{debug, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Advent of Code asks that private leaderboards be fetched at most once every 15 minutes.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Fetches the given private leaderboard, reusing a cached copy if it was fetched within the last 15 minutes.
+    pub fn fetch(year: u16, id: u64, session: &str) -> Result<Self> {
+        let cache_path = cache_path(year, id);
+        if let Some(json) = read_fresh(&cache_path) {
+            debug!("Using cached leaderboard from {}", cache_path.display());
+            return Self::parse(&json);
+        }
+
+        let json = client::get(
+            &format!("/{year}/leaderboard/private/view/{id}.json"),
+            session,
+        )?;
+        let leaderboard = Self::parse(&json)?;
+
+        if let Err(e) = write_cache(&cache_path, &json) {
+            warn!(
+                "Failed to cache leaderboard at {}: {e}",
+                cache_path.display()
+            );
+        }
+
+        Ok(leaderboard)
+    }
+
+    /// Members ordered by local score, then stars, then name.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.display_name().cmp(&b.display_name()))
+        });
+        members
+    }
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Advent of Code {}", self.event)?;
+        writeln!(f, "{:>4}  {:>5}  {:>5}  Name", "Rank", "Score", "Stars")?;
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:>5}  {:>5}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn cache_path(year: u16, id: u64) -> PathBuf {
+    config::cache_dir().join(format!("leaderboard-{year}-{id}.json"))
+}
+
+fn write_cache(path: &Path, json: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, json)
+}
+
+fn read_fresh(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    if age < CACHE_TTL {
+        fs::read_to_string(path).ok()
+    } else {
+        None
+    }
+}
+
+/// Fetches and prints the given private leaderboard.
+pub fn show(config: &config::Config, id: u64) -> Result<()> {
+    let year = config
+        .year
+        .ok_or_else(|| anyhow!("A year is required; set --year or {}", config::YEAR_ENV))?;
+    let session = config.session.as_deref().ok_or_else(|| {
+        anyhow!(
+            "A session cookie is required; set {} or `session` in the config file",
+            config::SESSION_ENV
+        )
+    })?;
+
+    print!("{}", Leaderboard::fetch(year, id, session)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 10, "local_score": 50, "global_score": 0, "last_star_ts": 0, "completion_day_level": {}},
+            "2": {"id": 2, "name": null, "stars": 12, "local_score": 50, "global_score": 0, "last_star_ts": 0, "completion_day_level": {}},
+            "3": {"id": 3, "name": "bob", "stars": 14, "local_score": 61, "global_score": 0, "last_star_ts": 0, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn test_leaderboard_ranked() -> Result<()> {
+        let leaderboard = Leaderboard::parse(JSON)?;
+        let names = leaderboard
+            .ranked()
+            .into_iter()
+            .map(Member::display_name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["bob", "(anonymous user #2)", "alice"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_leaderboard_display() -> Result<()> {
+        let leaderboard = Leaderboard::parse(JSON)?;
+        assert_eq!(
+            leaderboard.to_string(),
+            "\
+Advent of Code 2024
+Rank  Score  Stars  Name
+   1     61     14  bob
+   2     50     12  (anonymous user #2)
+   3     50     10  alice
+"
+        );
+        Ok(())
+    }
+}
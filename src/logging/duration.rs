@@ -0,0 +1,53 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// Displays a duration in the largest unit that keeps it readable, with a few significant digits: `850ns`, `41.3µs`,
+/// `1.2ms`, `3.46s`, `2m05s`, `1h02m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HumanDuration(pub Duration);
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let d = self.0;
+        let secs = d.as_secs();
+        if d < Duration::from_micros(1) {
+            write!(f, "{}ns", d.as_nanos())
+        } else if d < Duration::from_millis(1) {
+            write!(f, "{:.1}µs", d.as_secs_f64() * 1e6)
+        } else if d < Duration::from_secs(1) {
+            write!(f, "{:.1}ms", d.as_secs_f64() * 1e3)
+        } else if secs < 60 {
+            write!(f, "{:.2}s", d.as_secs_f64())
+        } else if secs < 3600 {
+            write!(f, "{}m{:02}s", secs / 60, secs % 60)
+        } else {
+            write!(f, "{}h{:02}m", secs / 3600, secs % 3600 / 60)
+        }
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_duration() {
+        let cases = [
+            (Duration::from_nanos(850), "850ns"),
+            (Duration::from_nanos(41_320), "41.3µs"),
+            (Duration::from_micros(1_204), "1.2ms"),
+            (Duration::from_millis(3_456), "3.46s"),
+            (Duration::from_secs(125), "2m05s"),
+            (Duration::from_secs(3_725), "1h02m"),
+        ];
+        for (duration, expected) in cases {
+            assert_eq!(HumanDuration(duration).to_string(), expected);
+        }
+    }
+}
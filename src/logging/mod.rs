@@ -1,20 +1,144 @@
-use env_logger::Target;
-use log::LevelFilter;
+use env_logger::fmt::Formatter;
+use env_logger::{Env, Target};
+use log::{LevelFilter, Record, debug};
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-pub(crate) fn init_logger(level: LevelFilter) -> anyhow::Result<()> {
-    init_logger_inner(level, false)
+mod duration;
+mod progress;
+
+pub use duration::HumanDuration;
+pub use progress::Progress;
+
+/// Initializes logging at `level`, with `filters` overriding it for specific targets (module paths). Directives in
+/// `RUST_LOG`, if set, take precedence over both.
+pub(crate) fn init_logger(
+    level: LevelFilter,
+    filters: &[(String, LevelFilter)],
+) -> anyhow::Result<()> {
+    init_logger_inner(level, filters, false)
 }
 
 pub fn init_test_logger() -> anyhow::Result<()> {
-    init_logger_inner(LevelFilter::Trace, true)
+    init_logger_inner(LevelFilter::Trace, &[], true)
+}
+
+/// Formats records as `elapsed LEVEL message`, with the level colored when writing to a terminal.
+fn format(buf: &mut Formatter, record: &Record, start: Instant) -> std::io::Result<()> {
+    let style = buf.default_level_style(record.level());
+    writeln!(
+        buf,
+        "{:>8.3}s {style}{:<5}{style:#} {}",
+        start.elapsed().as_secs_f64(),
+        record.level(),
+        record.args()
+    )
 }
 
-fn init_logger_inner(level: LevelFilter, is_test: bool) -> anyhow::Result<()> {
-    let _ = env_logger::builder()
-        .target(Target::Stdout)
-        .filter_level(level)
+fn init_logger_inner(
+    level: LevelFilter,
+    filters: &[(String, LevelFilter)],
+    is_test: bool,
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let mut builder = env_logger::builder();
+    builder.target(Target::Stdout).filter_level(level);
+    for (target, level) in filters {
+        builder.filter_module(target, *level);
+    }
+
+    // later directives for the same target replace earlier ones, so RUST_LOG wins
+    let _ = builder
+        .parse_env(Env::default())
+        .format(move |buf, record| format(buf, record, start))
         .is_test(is_test)
         .try_init();
 
     Ok(())
 }
+
+/// Logs how long it lived at debug level when dropped.
+#[derive(Debug)]
+pub struct ScopedTimer {
+    label: String,
+    start: Instant,
+}
+
+impl ScopedTimer {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            start: Instant::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for ScopedTimer {
+    fn drop(&mut self) {
+        debug!("{} took {}", self.label, HumanDuration(self.elapsed()));
+    }
+}
+
+/// Runs `f`, logging how long it took at debug level.
+pub fn timed<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let _timer = ScopedTimer::new(label);
+    f()
+}
+
+/// Logs through the given `log` macro only on the first and every `n`th execution of this call site, so diagnostics
+/// in hot loops don't flood the output. An `n` of 0 is treated as 1, logging every execution.
+///
+/// ```ignore
+/// log_every!(10_000, debug!, "visited {} states", seen.len());
+/// ```
+#[macro_export]
+macro_rules! log_every {
+    ($n:expr, $level:ident!, $($arg:tt)+) => {{
+        static CALLS: ::std::sync::atomic::AtomicU64 = ::std::sync::atomic::AtomicU64::new(0);
+        if $crate::logging::sample_every(&CALLS, $n as u64) {
+            $crate::__private::log::$level!($($arg)+);
+        }
+    }};
+}
+
+/// Counts a call in `calls` and returns whether it's the first or an `n`th one, for [`log_every!`].
+#[doc(hidden)]
+pub fn sample_every(calls: &AtomicU64, n: u64) -> bool {
+    calls
+        .fetch_add(1, Ordering::Relaxed)
+        .is_multiple_of(n.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampled(n: u64) -> Vec<u64> {
+        let calls = AtomicU64::new(0);
+        (0..10).filter(|_| sample_every(&calls, n)).collect()
+    }
+
+    #[test]
+    fn test_sample_every() {
+        assert_eq!(sampled(4), [0, 4, 8]);
+        assert_eq!(sampled(3), [0, 3, 6, 9]);
+        assert_eq!(sampled(0), (0..10).collect::<Vec<_>>());
+        assert_eq!(sampled(1), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_log_every() -> anyhow::Result<()> {
+        init_test_logger()?;
+        for i in 0..10 {
+            log_every!(4, trace!, "iteration {i}");
+            log_every!(3, info!, "info {}", i);
+            log_every!(0, trace!, "every {i}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use super::HumanDuration;
+use log::info;
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+/// The time aimed for between looks at the clock, which costs far more than counting.
+const CLOCK_CHECK_TARGET: Duration = Duration::from_millis(10);
+/// The most ticks between looks at the clock, however fast the loop.
+const MAX_CLOCK_CHECK_TICKS: u64 = 1024;
+
+/// Reports progress through a long loop at info level: the count, the rate and, when the total is known, an ETA.
+/// Reports are throttled to at most one per second. The clock is read every tick at first, and the interval doubles
+/// while batches finish in under 10ms (up to 1024 ticks) and halves when they take longer, so [`Progress::tick`] is
+/// cheap enough for inner loops yet slow loops still report.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    label: String,
+    count: u64,
+    total: Option<u64>,
+    clock_check_ticks: u64,
+    next_clock_check: u64,
+    last_clock_check: Instant,
+    start: Instant,
+    last_report: Instant,
+}
+
+impl Progress {
+    pub fn new(total: u64) -> Self {
+        Self::with_total(Some(total))
+    }
+
+    /// A reporter for loops of unknown length, which reports only the count and rate.
+    pub fn unbounded() -> Self {
+        Self::with_total(None)
+    }
+
+    fn with_total(total: Option<u64>) -> Self {
+        let now = Instant::now();
+        Self {
+            label: "progress".into(),
+            count: 0,
+            total,
+            clock_check_ticks: 1,
+            next_clock_check: 1,
+            last_clock_check: now,
+            start: now,
+            last_report: now,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn tick(&mut self) {
+        self.tick_n(1);
+    }
+
+    pub fn tick_n(&mut self, n: u64) {
+        self.count += n;
+        if self.count < self.next_clock_check {
+            return;
+        }
+
+        let now = Instant::now();
+        self.clock_check_ticks = next_clock_check_ticks(
+            self.clock_check_ticks,
+            now.duration_since(self.last_clock_check),
+        );
+        self.next_clock_check = self.count.saturating_add(self.clock_check_ticks);
+        self.last_clock_check = now;
+
+        if now.duration_since(self.last_report) >= REPORT_INTERVAL {
+            self.last_report = now;
+            self.report(now);
+        }
+    }
+
+    fn report(&self, now: Instant) {
+        info!("{}", self.message(now.duration_since(self.start)));
+    }
+
+    /// The report after `elapsed` time: the count, the rate and, when the total is known, the percentage and ETA.
+    fn message(&self, elapsed: Duration) -> String {
+        let rate = self.count as f64 / elapsed.as_secs_f64();
+        match self.total {
+            Some(total) => {
+                let percent = 100.0 * self.count as f64 / total as f64;
+                let eta =
+                    Duration::try_from_secs_f64(total.saturating_sub(self.count) as f64 / rate)
+                        .map_or_else(|_| "?".to_string(), |eta| HumanDuration(eta).to_string());
+                format!(
+                    "{}: {}/{total} ({percent:.1}%), {rate:.0}/s, ETA {eta}",
+                    self.label, self.count
+                )
+            }
+            None => format!("{}: {}, {rate:.0}/s", self.label, self.count),
+        }
+    }
+
+    /// Logs a final report regardless of throttling.
+    pub fn finish(&self) {
+        self.report(Instant::now());
+    }
+}
+
+/// The ticks until the next look at the clock, given the current interval and how long its batch took.
+fn next_clock_check_ticks(ticks: u64, elapsed: Duration) -> u64 {
+    if elapsed < CLOCK_CHECK_TARGET {
+        (ticks * 2).min(MAX_CLOCK_CHECK_TICKS)
+    } else if elapsed > CLOCK_CHECK_TARGET * 2 {
+        (ticks / 2).max(1)
+    } else {
+        ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let mut progress = Progress::new(10_000);
+        for _ in 0..1500 {
+            progress.tick();
+        }
+        progress.tick_n(2500);
+        assert_eq!(progress.count(), 4000);
+        assert!(progress.next_clock_check > 4000);
+        assert!(progress.next_clock_check <= 4000 + MAX_CLOCK_CHECK_TICKS);
+    }
+
+    #[test]
+    fn test_next_clock_check_ticks() {
+        let fast = Duration::from_micros(10);
+        let slow = Duration::from_millis(100);
+        assert_eq!(next_clock_check_ticks(1, fast), 2);
+        assert_eq!(next_clock_check_ticks(512, fast), 1024);
+        assert_eq!(next_clock_check_ticks(1024, fast), 1024);
+        assert_eq!(next_clock_check_ticks(1024, slow), 512);
+        assert_eq!(next_clock_check_ticks(1, slow), 1);
+        assert_eq!(next_clock_check_ticks(64, Duration::from_millis(15)), 64);
+    }
+
+    #[test]
+    fn test_slow_loop_checks_every_tick() {
+        let mut progress = Progress::unbounded();
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(25));
+            progress.tick();
+            assert_eq!(progress.next_clock_check, progress.count() + 1);
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        let mut progress = Progress::new(100_000).with_label("seeds");
+        progress.tick_n(41_000);
+        assert_eq!(
+            progress.message(Duration::from_secs(2)),
+            "seeds: 41000/100000 (41.0%), 20500/s, ETA 2.88s"
+        );
+
+        // nothing done yet, so there is no rate to estimate from
+        let progress = Progress::new(10);
+        assert_eq!(
+            progress.message(Duration::from_secs(1)),
+            "progress: 0/10 (0.0%), 0/s, ETA ?"
+        );
+
+        let mut progress = Progress::unbounded().with_label("states");
+        progress.tick_n(1_500);
+        assert_eq!(
+            progress.message(Duration::from_millis(500)),
+            "states: 1500, 3000/s"
+        );
+    }
+}
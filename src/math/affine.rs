@@ -0,0 +1,149 @@
+/// The map `x -> a*x + b (mod n)`. Affine maps compose into affine maps, so a long list of steps — a shuffle of a
+/// deck of `n` cards, say — collapses into one, which can then be repeated `k` times in `O(log k)` or inverted to ask
+/// where a position came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Affine {
+    pub a: u64,
+    pub b: u64,
+    pub n: u64,
+}
+
+impl Affine {
+    /// The map `x -> a*x + b (mod n)`, with negative coefficients taken mod `n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn new(a: i128, b: i128, n: u64) -> Self {
+        assert!(n > 0, "modulus must be positive");
+        let modulus = i128::from(n);
+        Self {
+            a: a.rem_euclid(modulus) as u64,
+            b: b.rem_euclid(modulus) as u64,
+            n,
+        }
+    }
+
+    pub fn identity(n: u64) -> Self {
+        Self::new(1, 0, n)
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        ((u128::from(self.a) * u128::from(x) + u128::from(self.b)) % u128::from(self.n)) as u64
+    }
+
+    /// The map applying `self`, then `next`.
+    ///
+    /// # Panics
+    ///
+    /// If the moduli differ.
+    pub fn then(&self, next: &Affine) -> Affine {
+        assert_eq!(self.n, next.n, "can't compose maps with different moduli");
+        Affine {
+            a: mul_mod(next.a, self.a, self.n),
+            b: next.apply(self.b),
+            n: self.n,
+        }
+    }
+
+    /// The map applied `k` times in a row, by repeated squaring.
+    pub fn pow(&self, mut k: u64) -> Affine {
+        let mut result = Affine::identity(self.n);
+        let mut square = *self;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            k >>= 1;
+        }
+        result
+    }
+
+    /// The map undoing this one, if `a` is invertible mod `n`.
+    pub fn inverse(&self) -> Option<Affine> {
+        let a_inv = mod_inverse(self.a, self.n)?;
+        // x = a⁻¹·(y - b)
+        let b = mul_mod(a_inv, self.n - self.b % self.n, self.n);
+        Some(Affine {
+            a: a_inv,
+            b,
+            n: self.n,
+        })
+    }
+}
+
+fn mul_mod(x: u64, y: u64, n: u64) -> u64 {
+    (u128::from(x) * u128::from(y) % u128::from(n)) as u64
+}
+
+/// The `x` in `0..n` with `a*x ≡ 1 (mod n)`, if `a` and `n` are coprime.
+pub fn mod_inverse(a: u64, n: u64) -> Option<u64> {
+    // extended Euclid, tracking only the coefficient of `a`
+    let (mut r0, mut r1) = (i128::from(n), i128::from(a % n));
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(i128::from(n)) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // 2019 day 22 techniques, as maps from a card's position before to its position after
+    fn new_stack(n: u64) -> Affine {
+        Affine::new(-1, -1, n)
+    }
+
+    fn cut(k: i128, n: u64) -> Affine {
+        Affine::new(1, -k, n)
+    }
+
+    fn increment(k: i128, n: u64) -> Affine {
+        Affine::new(k, 0, n)
+    }
+
+    fn deck(shuffle: &Affine) -> Vec<u64> {
+        let mut deck = vec![0; shuffle.n as usize];
+        for card in 0..shuffle.n {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        deck
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let shuffle = [cut(6, 10), increment(7, 10), new_stack(10)]
+            .iter()
+            .fold(Affine::identity(10), |s, step| s.then(step));
+        assert_eq!(deck(&shuffle), [3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+    }
+
+    #[test]
+    fn test_pow_and_inverse() {
+        let n = 10_007;
+        let shuffle = increment(7, n).then(&cut(-2, n)).then(&new_stack(n));
+        let repeated = (0..1000).fold(Affine::identity(n), |s, _| s.then(&shuffle));
+        assert_eq!(shuffle.pow(1000), repeated);
+        assert_eq!(shuffle.pow(0), Affine::identity(n));
+
+        let inverse = shuffle.inverse().unwrap();
+        assert_eq!(shuffle.then(&inverse), Affine::identity(n));
+        assert_eq!(inverse.apply(shuffle.apply(2019)), 2019);
+        assert!(Affine::new(2, 1, 10).inverse().is_none());
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+    }
+}
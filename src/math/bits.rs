@@ -0,0 +1,122 @@
+/// The unsigned integer types the helpers in this module accept.
+pub trait Bits: Copy {
+    const BITS: u32;
+
+    fn to_u128(self) -> u128;
+
+    /// Keeps the low `Self::BITS` bits of `value`.
+    fn from_u128_truncating(value: u128) -> Self;
+}
+
+macro_rules! impl_bits {
+    ($($t:ty),*) => {
+        $(impl Bits for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            fn from_u128_truncating(value: u128) -> Self {
+                value as $t
+            }
+        })*
+    };
+}
+
+impl_bits!(u8, u16, u32, u64, u128, usize);
+
+fn mask(len: u32) -> u128 {
+    if len >= u128::BITS {
+        u128::MAX
+    } else {
+        (1 << len) - 1
+    }
+}
+
+/// The `len` bits of `value` starting at bit `start`, counting from the least significant bit.
+pub fn bit_range<T: Bits>(value: T, start: u32, len: u32) -> T {
+    let shifted = value.to_u128().checked_shr(start).unwrap_or(0);
+    T::from_u128_truncating(shifted & mask(len))
+}
+
+/// Reverses the low `width` bits of `value`, e.g. `0b0011` with width 4 becomes `0b1100`. Higher bits are dropped.
+pub fn reverse_bits<T: Bits>(value: T, width: u32) -> T {
+    if width == 0 {
+        return T::from_u128_truncating(0);
+    }
+    let reversed = value.to_u128().reverse_bits() >> (u128::BITS - width.min(u128::BITS));
+    T::from_u128_truncating(reversed)
+}
+
+/// The positions of the set bits of `value`, lowest first.
+pub fn set_bits<T: Bits>(value: T) -> impl Iterator<Item = u32> {
+    let mut rest = value.to_u128();
+    core::iter::from_fn(move || {
+        (rest != 0).then(|| {
+            let bit = rest.trailing_zeros();
+            rest &= rest - 1;
+            bit
+        })
+    })
+}
+
+/// Builds a number from bits given most significant first, like the digits of a binary literal. Bits beyond
+/// `T::BITS` shift the earliest ones out.
+pub fn from_bits<T: Bits>(bits: impl IntoIterator<Item = bool>) -> T {
+    let value = bits
+        .into_iter()
+        .fold(0u128, |acc, bit| (acc << 1) | u128::from(bit));
+    T::from_u128_truncating(value)
+}
+
+/// The low `width` bits of `value`, most significant first. The inverse of [`from_bits`].
+pub fn to_bits<T: Bits>(value: T, width: u32) -> impl Iterator<Item = bool> {
+    let value = value.to_u128();
+    (0..width)
+        .rev()
+        .map(move |i| value.checked_shr(i).unwrap_or(0) & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_bit_range() {
+        assert_eq!(bit_range(0b1101_0110u8, 1, 3), 0b011);
+        assert_eq!(bit_range(0b1101_0110u8, 4, 8), 0b1101);
+        assert_eq!(bit_range(u128::MAX, 0, 128), u128::MAX);
+        assert_eq!(bit_range(u64::MAX, 64, 8), 0);
+    }
+
+    #[test]
+    fn test_reverse_bits() {
+        assert_eq!(reverse_bits(0b0011u8, 4), 0b1100);
+        assert_eq!(reverse_bits(0b10110u32, 5), 0b01101);
+        assert_eq!(reverse_bits(0b1111_0001u8, 4), 0b1000);
+        assert_eq!(reverse_bits(1u128, 128), 1 << 127);
+        assert_eq!(reverse_bits(7u16, 0), 0);
+    }
+
+    #[test]
+    fn test_set_bits() {
+        assert_eq!(set_bits(0b1010_0101u8).collect::<Vec<_>>(), [0, 2, 5, 7]);
+        assert_eq!(set_bits(1u128 << 100).collect::<Vec<_>>(), [100]);
+        assert_eq!(set_bits(0usize).count(), 0);
+    }
+
+    #[test]
+    fn test_from_and_to_bits() {
+        // 2021 day 3: gamma rate from the most common bit in each column
+        let gamma: u32 = from_bits("10110".chars().map(|c| c == '1'));
+        assert_eq!(gamma, 22);
+        assert_eq!(
+            to_bits(gamma, 5).collect::<Vec<_>>(),
+            [true, false, true, true, false]
+        );
+        assert_eq!(from_bits::<u8>(to_bits(gamma, 5)), 22);
+        assert_eq!(from_bits::<u8>([true; 9]), u8::MAX);
+    }
+}
@@ -0,0 +1,306 @@
+use super::{three_dimensional, two_dimensional};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+
+/// A point with integer coordinates that a [`KdTree`] can index.
+pub trait KdPoint: Copy {
+    const DIMENSIONS: usize;
+
+    fn coordinate(&self, axis: usize) -> i64;
+}
+
+macro_rules! impl_kd_point {
+    ($($t:ty),*) => {$(
+        impl KdPoint for two_dimensional::Point<$t> {
+            const DIMENSIONS: usize = 2;
+
+            fn coordinate(&self, axis: usize) -> i64 {
+                [self.x, self.y][axis] as i64
+            }
+        }
+
+        impl KdPoint for three_dimensional::Point<$t> {
+            const DIMENSIONS: usize = 3;
+
+            fn coordinate(&self, axis: usize) -> i64 {
+                [self.x, self.y, self.z][axis] as i64
+            }
+        }
+    )*};
+}
+
+impl_kd_point!(i32, i64, isize, u32, usize);
+
+/// How [`KdTree`] queries measure distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Sum of the absolute coordinate differences
+    Manhattan,
+    /// Squared straight-line distance, which orders points the same as the distance itself
+    SquaredEuclidean,
+}
+
+impl Metric {
+    pub fn distance<P: KdPoint>(self, a: &P, b: &P) -> u128 {
+        (0..P::DIMENSIONS)
+            .map(|axis| self.axis_distance(a.coordinate(axis), b.coordinate(axis)))
+            .sum()
+    }
+
+    /// The least distance between a point at `a` and any point at `b` along one axis, for pruning.
+    fn axis_distance(self, a: i64, b: i64) -> u128 {
+        let d = u128::from(a.abs_diff(b));
+        match self {
+            Metric::Manhattan => d,
+            Metric::SquaredEuclidean => d * d,
+        }
+    }
+}
+
+/// A k-d tree over 2D or 3D points, for nearest-neighbor and range queries on point sets too large for pairwise
+/// scans. The tree is built once and is immutable; points are stored in a single `Vec` ordered so that each subslice's
+/// median splits it on the axis for its depth.
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    points: Vec<P>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(mut points: Vec<P>) -> Self {
+        build(&mut points, 0);
+        Self { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// The point nearest `target` and its distance. Which of several equally near points is returned is unspecified;
+    /// use [`k_nearest`](Self::k_nearest) with `k = 2` to detect ties.
+    pub fn nearest(&self, target: &P, metric: Metric) -> Option<(&P, u128)> {
+        self.k_nearest(target, 1, metric).into_iter().next()
+    }
+
+    /// The `k` points nearest `target` with their distances, nearest first.
+    pub fn k_nearest(&self, target: &P, k: usize, metric: Metric) -> Vec<(&P, u128)> {
+        let mut search = NearestSearch {
+            points: &self.points,
+            target,
+            k,
+            metric,
+            best: BinaryHeap::new(),
+        };
+        if k > 0 {
+            search.visit(0, self.points.len(), 0);
+        }
+        search
+            .best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(d, i)| (&self.points[i], d))
+            .collect()
+    }
+
+    /// Every point within `radius` of `target`, inclusive.
+    pub fn within(&self, target: &P, radius: u128, metric: Metric) -> Vec<&P> {
+        let mut found = Vec::new();
+        search_within(&self.points, 0, target, radius, metric, &mut found);
+        found
+    }
+
+    /// Every point inside the axis-aligned box with corners `min` and `max`, inclusive.
+    pub fn in_box(&self, min: &P, max: &P) -> Vec<&P> {
+        let mut found = Vec::new();
+        search_box(&self.points, 0, min, max, &mut found);
+        found
+    }
+}
+
+/// State for a k-nearest query, keeping the `k` nearest points found so far as (distance, index) pairs.
+struct NearestSearch<'a, P> {
+    points: &'a [P],
+    target: &'a P,
+    k: usize,
+    metric: Metric,
+    best: BinaryHeap<(u128, usize)>,
+}
+
+impl<P: KdPoint> NearestSearch<'_, P> {
+    /// Searches the subtree stored in `points[lo..hi]`.
+    fn visit(&mut self, lo: usize, hi: usize, depth: usize) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let axis = depth % P::DIMENSIONS;
+        let point = &self.points[mid];
+
+        let d = self.metric.distance(point, self.target);
+        if self.best.len() < self.k {
+            self.best.push((d, mid));
+        } else if self.best.peek().is_some_and(|&(worst, _)| d < worst) {
+            self.best.pop();
+            self.best.push((d, mid));
+        }
+
+        let (t, p) = (self.target.coordinate(axis), point.coordinate(axis));
+        let (near, far) = if t < p {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.visit(near.0, near.1, depth + 1);
+        let bound = self.metric.axis_distance(t, p);
+        if self.best.len() < self.k || self.best.peek().is_some_and(|&(worst, _)| bound < worst) {
+            self.visit(far.0, far.1, depth + 1);
+        }
+    }
+}
+
+fn build<P: KdPoint>(points: &mut [P], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let mid = points.len() / 2;
+    let axis = depth % P::DIMENSIONS;
+    points.select_nth_unstable_by_key(mid, |p| p.coordinate(axis));
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+fn search_within<'a, P: KdPoint>(
+    slice: &'a [P],
+    depth: usize,
+    target: &P,
+    radius: u128,
+    metric: Metric,
+    found: &mut Vec<&'a P>,
+) {
+    if slice.is_empty() {
+        return;
+    }
+    let mid = slice.len() / 2;
+    let axis = depth % P::DIMENSIONS;
+    let point = &slice[mid];
+    if metric.distance(point, target) <= radius {
+        found.push(point);
+    }
+
+    let (t, p) = (target.coordinate(axis), point.coordinate(axis));
+    let reaches_other_side = metric.axis_distance(t, p) <= radius;
+    if t < p || reaches_other_side {
+        search_within(&slice[..mid], depth + 1, target, radius, metric, found);
+    }
+    if t >= p || reaches_other_side {
+        search_within(&slice[mid + 1..], depth + 1, target, radius, metric, found);
+    }
+}
+
+fn search_box<'a, P: KdPoint>(
+    slice: &'a [P],
+    depth: usize,
+    min: &P,
+    max: &P,
+    found: &mut Vec<&'a P>,
+) {
+    if slice.is_empty() {
+        return;
+    }
+    let mid = slice.len() / 2;
+    let axis = depth % P::DIMENSIONS;
+    let point = &slice[mid];
+    if (0..P::DIMENSIONS)
+        .all(|a| (min.coordinate(a)..=max.coordinate(a)).contains(&point.coordinate(a)))
+    {
+        found.push(point);
+    }
+
+    let p = point.coordinate(axis);
+    if min.coordinate(axis) <= p {
+        search_box(&slice[..mid], depth + 1, min, max, found);
+    }
+    if max.coordinate(axis) >= p {
+        search_box(&slice[mid + 1..], depth + 1, min, max, found);
+    }
+}
+
+impl<P: KdPoint> FromIterator<P> for KdTree<P> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::three_dimensional::IPoint as IPoint3;
+    use crate::math::two_dimensional::IPoint;
+
+    /// A deterministic spread of points, so brute force can check the tree.
+    fn scattered(n: i64) -> Vec<IPoint3> {
+        (0..n)
+            .map(|i| IPoint3::new(i * 37 % 101 - 50, i * 53 % 89 - 44, i * 71 % 97 - 48))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let points = scattered(500);
+        let tree = points.iter().copied().collect::<KdTree<_>>();
+        assert_eq!(tree.len(), 500);
+
+        for metric in [Metric::Manhattan, Metric::SquaredEuclidean] {
+            for target in scattered(40).iter().map(|&p| p + IPoint3::new(3, -2, 1)) {
+                let mut expected = points
+                    .iter()
+                    .map(|p| metric.distance(p, &target))
+                    .collect::<Vec<_>>();
+                expected.sort();
+
+                let nearest = tree.k_nearest(&target, 3, metric);
+                let distances = nearest.iter().map(|&(_, d)| d).collect::<Vec<_>>();
+                assert_eq!(distances, expected[..3]);
+                assert_eq!(
+                    tree.nearest(&target, metric).map(|(_, d)| d),
+                    Some(expected[0])
+                );
+
+                let within = tree.within(&target, expected[10], metric).len();
+                assert_eq!(
+                    within,
+                    expected.iter().filter(|&&d| d <= expected[10]).count()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_box() {
+        // 2018 day 6 coordinates
+        let points = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)].map(IPoint::from);
+        let tree = KdTree::new(points.to_vec());
+        let mut found = tree.in_box(&IPoint::new(1, 3), &IPoint::new(5, 6));
+        found.sort();
+        assert_eq!(
+            found,
+            [&IPoint::new(1, 6), &IPoint::new(3, 4), &IPoint::new(5, 5)]
+        );
+
+        let (nearest, d) = tree.nearest(&IPoint::new(0, 0), Metric::Manhattan).unwrap();
+        assert_eq!((*nearest, d), (IPoint::new(1, 1), 2));
+        assert!(
+            KdTree::<IPoint>::new(Vec::new())
+                .nearest(&IPoint::new(0, 0), Metric::Manhattan)
+                .is_none()
+        );
+    }
+}
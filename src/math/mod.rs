@@ -1,9 +1,21 @@
-use anyhow::anyhow;
-use anyhow::bail;
-use std::cmp::{max, min};
-use std::fmt::{Debug, Display, Formatter};
-use std::ops::{Add, Div, Mul, Rem, Sub};
-use std::str::FromStr;
+use crate::error::{Error, Result, column_of};
+use alloc::format;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Add, Div, Mul, Rem, Sub};
+use core::str::FromStr;
+
+mod affine;
+pub mod bits;
+mod kd_tree;
+mod rng;
+mod voxels;
+
+pub use affine::{Affine, mod_inverse};
+pub use kd_tree::{KdPoint, KdTree, Metric};
+pub use rng::Rng;
+pub use voxels::{exterior_surface_area, flood_fill3, surface_area};
 
 pub trait ToF64: Copy {
     fn to_f64(self) -> f64;
@@ -19,21 +31,64 @@ macro_rules! impl_to_f64 {
 
 impl_to_f64!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
 
+/// Parses `part`, one coordinate of the point `s`, with errors pointing at its column.
+fn parse_coordinate<T>(s: &str, part: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    part.parse().map_err(|e| {
+        let error = Error::parse(format!("Invalid coordinate {part:?}: {e}"));
+        match column_of(s, part) {
+            Some(column) => error.at_column(column, s),
+            None => error,
+        }
+    })
+}
+
 pub mod two_dimensional {
     use super::*;
 
     #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Point<T = usize> {
         pub x: T,
         pub y: T,
     }
 
+    /// Grid-style coordinates, the default.
+    pub type UPoint = Point<usize>;
+    /// Signed coordinates, for puzzles that extend past the origin.
+    pub type IPoint = Point<i64>;
+
     impl<T> Point<T> {
         pub fn new(x: T, y: T) -> Self {
             Self { x, y }
         }
+
+        /// Converts each coordinate to `U`, or `None` if either doesn't fit (e.g. a negative `i64` as `usize`).
+        pub fn cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+            Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+        }
     }
 
+    impl<T: Add<Output = T>> Add for Point<T> {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self {
+            Self::new(self.x + other.x, self.y + other.y)
+        }
+    }
+
+    impl<T: Sub<Output = T>> Sub for Point<T> {
+        type Output = Self;
+
+        fn sub(self, other: Self) -> Self {
+            Self::new(self.x - other.x, self.y - other.y)
+        }
+    }
+
+    #[cfg(feature = "std")]
     impl<T: ToF64> Point<T> {
         pub fn distance(&self, other: &Self) -> f64 {
             let dx = self.x.to_f64() - other.x.to_f64();
@@ -67,14 +122,20 @@ pub mod two_dimensional {
         }
     }
 
+    impl<T> From<(T, T)> for Point<T> {
+        fn from((x, y): (T, T)) -> Self {
+            Self::new(x, y)
+        }
+    }
+
     impl<T: Display> Display for Point<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             write!(f, "({}, {})", self.x, self.y)
         }
     }
 
     impl<T: Display> Debug for Point<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             write!(f, "{self}")
         }
     }
@@ -82,13 +143,15 @@ pub mod two_dimensional {
     impl<T> FromStr for Point<T>
     where
         T: FromStr,
-        T::Err: std::error::Error + Send + Sync + 'static,
+        T::Err: Display,
     {
-        type Err = anyhow::Error;
+        type Err = Error;
 
-        fn from_str(s: &str) -> anyhow::Result<Self> {
-            let (x, y) = s.split_once(',').ok_or_else(|| anyhow!("Invalid point"))?;
-            Ok(Self::new(x.parse()?, y.parse()?))
+        fn from_str(s: &str) -> Result<Self> {
+            let (x, y) = s
+                .split_once(',')
+                .ok_or_else(|| Error::parse(format!("Invalid point {s:?}")))?;
+            Ok(Self::new(parse_coordinate(s, x)?, parse_coordinate(s, y)?))
         }
     }
 }
@@ -97,18 +160,63 @@ pub mod three_dimensional {
     use super::*;
 
     #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Point<T = usize> {
         pub x: T,
         pub y: T,
         pub z: T,
     }
 
+    pub type UPoint = Point<usize>;
+    pub type IPoint = Point<i64>;
+
     impl<T> Point<T> {
         pub fn new(x: T, y: T, z: T) -> Self {
             Self { x, y, z }
         }
+
+        /// Converts each coordinate to `U`, or `None` if any doesn't fit.
+        pub fn cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+            Some(Point::new(
+                self.x.try_into().ok()?,
+                self.y.try_into().ok()?,
+                self.z.try_into().ok()?,
+            ))
+        }
+    }
+
+    impl IPoint {
+        /// The six points sharing a face with this one.
+        pub fn face_neighbors(&self) -> [Self; 6] {
+            let Self { x, y, z } = *self;
+            [
+                Self::new(x - 1, y, z),
+                Self::new(x + 1, y, z),
+                Self::new(x, y - 1, z),
+                Self::new(x, y + 1, z),
+                Self::new(x, y, z - 1),
+                Self::new(x, y, z + 1),
+            ]
+        }
+    }
+
+    impl<T: Add<Output = T>> Add for Point<T> {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self {
+            Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+        }
     }
 
+    impl<T: Sub<Output = T>> Sub for Point<T> {
+        type Output = Self;
+
+        fn sub(self, other: Self) -> Self {
+            Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+        }
+    }
+
+    #[cfg(feature = "std")]
     impl<T: ToF64> Point<T> {
         pub fn distance(&self, other: &Self) -> f64 {
             let dx = self.x.to_f64() - other.x.to_f64();
@@ -148,14 +256,20 @@ pub mod three_dimensional {
         }
     }
 
+    impl<T> From<(T, T, T)> for Point<T> {
+        fn from((x, y, z): (T, T, T)) -> Self {
+            Self::new(x, y, z)
+        }
+    }
+
     impl<T: Display> Display for Point<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             write!(f, "({}, {}, {})", self.x, self.y, self.z)
         }
     }
 
     impl<T: Display> Debug for Point<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             write!(f, "{self}")
         }
     }
@@ -163,19 +277,19 @@ pub mod three_dimensional {
     impl<T> FromStr for Point<T>
     where
         T: FromStr,
-        T::Err: std::error::Error + Send + Sync + 'static,
+        T::Err: Display,
     {
-        type Err = anyhow::Error;
+        type Err = Error;
 
-        fn from_str(s: &str) -> anyhow::Result<Self> {
+        fn from_str(s: &str) -> Result<Self> {
             let parts = s.split(',').collect::<Vec<_>>();
             if parts.len() != 3 {
-                bail!("Invalid point");
+                return Err(Error::parse(format!("Invalid point {s:?}")));
             }
 
-            let x = parts[0].parse()?;
-            let y = parts[1].parse()?;
-            let z = parts[2].parse()?;
+            let x = parse_coordinate(s, parts[0])?;
+            let y = parse_coordinate(s, parts[1])?;
+            let z = parse_coordinate(s, parts[2])?;
 
             Ok(Self::new(x, y, z))
         }
@@ -183,6 +297,7 @@ pub mod three_dimensional {
 }
 
 #[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MinMax<T> {
     pub min: Option<T>,
     pub max: Option<T>,
@@ -225,7 +340,7 @@ where
 
     // Ensure a >= b
     if b > a {
-        std::mem::swap(&mut a, &mut b);
+        core::mem::swap(&mut a, &mut b);
     }
 
     // Euclidean algorithm (iterative)
@@ -248,6 +363,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
 
     #[test]
     fn test_least_common_multiple() {
@@ -300,6 +417,25 @@ mod tests {
         assert_eq!(p3.manhattan_distance(&p4), 11);
     }
 
+    #[test]
+    fn test_point_arithmetic_and_cast() {
+        use two_dimensional::{IPoint, UPoint};
+
+        let a = IPoint::new(3, -2);
+        let b = IPoint::new(1 << 40, 7);
+        assert_eq!(a + b - b, a);
+        assert_eq!(a.cast::<usize>(), None);
+        assert_eq!((a + IPoint::new(0, 5)).cast(), Some(UPoint::new(3, 3)));
+        assert_eq!(
+            UPoint::from((4, 5)).cast::<i128>(),
+            Some((4i128, 5i128).into())
+        );
+
+        let p = three_dimensional::IPoint::new(1, 2, 3);
+        assert_eq!(p + p - p, p);
+        assert_eq!(p.cast::<u8>(), Some(three_dimensional::Point::new(1, 2, 3)));
+    }
+
     #[test]
     fn test_manhattan_distance_3d() {
         let p1 = three_dimensional::Point::new(0usize, 0, 0);
@@ -326,6 +462,26 @@ mod tests {
         assert_eq!(p.z, -3);
     }
 
+    #[test]
+    fn test_point_parse_error_column() {
+        let err = "12,3,y"
+            .parse::<three_dimensional::Point<i64>>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Parse {
+                column: Some(6),
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Invalid coordinate \"y\": invalid digit found in string at column 6:\n12,3,y\n     ^"
+        );
+        assert!("12;3".parse::<two_dimensional::Point<i64>>().is_err());
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_point_distance() {
         let p1 = two_dimensional::Point::new(0usize, 0);
@@ -336,4 +492,28 @@ mod tests {
         let p4 = three_dimensional::Point::new(2i64, 3, 6);
         assert_eq!(p4.distance(&p3), 7.0);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() -> anyhow::Result<()> {
+        let point = two_dimensional::Point::new(3i32, -4);
+        let json = serde_json::to_string(&point)?;
+        assert_eq!(json, r#"{"x":3,"y":-4}"#);
+        assert_eq!(
+            serde_json::from_str::<two_dimensional::Point<i32>>(&json)?,
+            point
+        );
+
+        let point = three_dimensional::Point::new(1usize, 2, 3);
+        let json = serde_json::to_string(&point)?;
+        assert_eq!(
+            serde_json::from_str::<three_dimensional::Point>(&json)?,
+            point
+        );
+
+        let bounds: MinMax<u8> = [4, 1, 9].into_iter().collect();
+        let bounds: MinMax<u8> = serde_json::from_str(&serde_json::to_string(&bounds)?)?;
+        assert_eq!((bounds.min, bounds.max), (Some(1), Some(9)));
+        Ok(())
+    }
 }
@@ -0,0 +1,133 @@
+use core::ops::Range;
+
+/// A small seedable pseudo-random generator (xorshift64*), for randomized algorithms that should behave the same on
+/// every run. Not suitable for cryptography.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// A generator whose output depends only on `seed`; any seed, including 0, is fine.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            // the state must never be 0, which xorshift can't leave
+            state: splitmix64(seed).max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// A uniform value in `0..n`, without modulo bias.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick a value below 0");
+        // Lemire's multiply-and-reject method
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(n);
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// A uniform value in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "can't pick from an empty range");
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// A uniform value in `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Shuffles `items` in place, uniformly (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// The SplitMix64 finalizer, which turns a counter or seed into well-distributed bits.
+fn splitmix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = Rng::new(2024);
+        let mut b = Rng::new(2024);
+        let xs = [(); 8].map(|_| a.next_u64());
+        assert_eq!(xs, [(); 8].map(|_| b.next_u64()));
+        assert_ne!(xs[0], Rng::new(2025).next_u64());
+        assert_ne!(Rng::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(7);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.below(6) as usize] += 1;
+        }
+        assert!(
+            counts.iter().all(|&c| (850..1150).contains(&c)),
+            "{counts:?}"
+        );
+
+        for _ in 0..1000 {
+            assert!((-5..5).contains(&rng.range(-5..5)));
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+        }
+        assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+        assert!(rng.choose::<u8>(&[]).is_none());
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut items = [1, 2, 3, 4, 5, 6, 7, 8];
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!(items, [1, 2, 3, 4, 5, 6, 7, 8]);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
@@ -0,0 +1,104 @@
+use super::MinMax;
+use super::three_dimensional::IPoint;
+use alloc::collections::{BTreeSet, VecDeque};
+
+/// Every point reachable from `start` through face-adjacent points that are `open` and inside the inclusive box
+/// `min..=max`, including `start` itself if it qualifies.
+pub fn flood_fill3<F>(start: IPoint, min: IPoint, max: IPoint, mut open: F) -> BTreeSet<IPoint>
+where
+    F: FnMut(&IPoint) -> bool,
+{
+    let in_bounds = |p: &IPoint| {
+        (min.x..=max.x).contains(&p.x)
+            && (min.y..=max.y).contains(&p.y)
+            && (min.z..=max.z).contains(&p.z)
+    };
+
+    let mut filled = BTreeSet::new();
+    if !in_bounds(&start) || !open(&start) {
+        return filled;
+    }
+    filled.insert(start);
+    let mut queue = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        for next in p.face_neighbors() {
+            if in_bounds(&next) && !filled.contains(&next) && open(&next) {
+                filled.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+    filled
+}
+
+/// The number of cube faces not shared with another cube, counting faces of enclosed air pockets.
+pub fn surface_area(cubes: &BTreeSet<IPoint>) -> usize {
+    cubes
+        .iter()
+        .flat_map(IPoint::face_neighbors)
+        .filter(|n| !cubes.contains(n))
+        .count()
+}
+
+/// The number of cube faces reachable from outside, i.e. [`surface_area`] without enclosed air pockets. Fills the air
+/// around the cubes from a corner of their bounding box grown by one, so the outside is connected.
+pub fn exterior_surface_area(cubes: &BTreeSet<IPoint>) -> usize {
+    if cubes.is_empty() {
+        return 0;
+    }
+    let bound = |axis: fn(&IPoint) -> i64| {
+        let range = cubes.iter().map(axis).collect::<MinMax<_>>();
+        (range.min.unwrap_or(0) - 1, range.max.unwrap_or(0) + 1)
+    };
+    let ((x0, x1), (y0, y1), (z0, z1)) = (bound(|p| p.x), bound(|p| p.y), bound(|p| p.z));
+
+    let min = IPoint::new(x0, y0, z0);
+    let max = IPoint::new(x1, y1, z1);
+    let outside = flood_fill3(min, min, max, |p| !cubes.contains(p));
+    outside
+        .iter()
+        .flat_map(IPoint::face_neighbors)
+        .filter(|n| cubes.contains(n))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2022 day 18 example droplet
+    const DROPLET: [(i64, i64, i64); 13] = [
+        (2, 2, 2),
+        (1, 2, 2),
+        (3, 2, 2),
+        (2, 1, 2),
+        (2, 3, 2),
+        (2, 2, 1),
+        (2, 2, 3),
+        (2, 2, 4),
+        (2, 2, 6),
+        (1, 2, 5),
+        (3, 2, 5),
+        (2, 1, 5),
+        (2, 3, 5),
+    ];
+
+    #[test]
+    fn test_surface_area() {
+        let cubes = DROPLET.into_iter().map(IPoint::from).collect();
+        assert_eq!(surface_area(&cubes), 64);
+        assert_eq!(exterior_surface_area(&cubes), 58);
+        assert_eq!(exterior_surface_area(&BTreeSet::new()), 0);
+    }
+
+    #[test]
+    fn test_flood_fill3() {
+        let origin = IPoint::new(0, 0, 0);
+        let corner = IPoint::new(2, 2, 2);
+        // everything but a wall at x = 1
+        let filled = flood_fill3(origin, origin, corner, |p| p.x != 1);
+        assert_eq!(filled.len(), 9);
+        assert!(filled.iter().all(|p| p.x == 0));
+        assert!(flood_fill3(IPoint::new(3, 0, 0), origin, corner, |_| true).is_empty());
+    }
+}
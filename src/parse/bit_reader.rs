@@ -0,0 +1,158 @@
+use super::hex::hex_digit;
+use crate::error::{Error, Result};
+use std::borrow::Cow;
+
+/// Reads unsigned integers of any width up to 64 bits from a byte string, most significant bit first, as packet and
+/// bitstream formats are specified.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data: Cow<'a, [u8]>,
+    // Bit offsets into `data`; a sub-reader shares its parent's data, so `start` may be past 0
+    start: usize,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            end: data.len() * 8,
+            data: Cow::Borrowed(data),
+            start: 0,
+            pos: 0,
+        }
+    }
+
+    /// Reads the bits spelled out by a hex string such as `"D2FE28"`, ignoring surrounding whitespace.
+    pub fn from_hex(hex: &str) -> Result<BitReader<'static>> {
+        let digits = hex
+            .trim()
+            .chars()
+            .map(|c| hex_digit(c, hex))
+            .collect::<Result<Vec<_>>>()?;
+        let bytes = digits
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        Ok(BitReader {
+            end: digits.len() * 4,
+            data: Cow::Owned(bytes),
+            start: 0,
+            pos: 0,
+        })
+    }
+
+    /// Bits consumed so far, counted from the start of this reader.
+    pub fn position(&self) -> usize {
+        self.pos - self.start
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Reads the next `width` bits as an unsigned integer.
+    pub fn read(&mut self, width: u32) -> Result<u64> {
+        if width > u64::BITS {
+            return Err(Error::parse(format!("Can't read {width} bits into a u64")));
+        }
+        self.check_remaining(width as usize)?;
+
+        let mut value = 0;
+        for _ in 0..width {
+            let bit = self.data[self.pos / 8] >> (7 - self.pos % 8) & 1;
+            value = value << 1 | u64::from(bit);
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool> {
+        Ok(self.read(1)? == 1)
+    }
+
+    pub fn skip(&mut self, bits: usize) -> Result<()> {
+        self.check_remaining(bits)?;
+        self.pos += bits;
+        Ok(())
+    }
+
+    /// Consumes the next `bits` bits, returning a reader over just them.
+    pub fn sub_reader(&mut self, bits: usize) -> Result<BitReader<'_>> {
+        let start = self.pos;
+        self.skip(bits)?;
+        Ok(BitReader {
+            data: Cow::Borrowed(&self.data),
+            start,
+            pos: start,
+            end: start + bits,
+        })
+    }
+
+    fn check_remaining(&self, bits: usize) -> Result<()> {
+        if bits > self.remaining() {
+            return Err(Error::parse(format!(
+                "Can't read {bits} bits with {} left",
+                self.remaining()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> Result<()> {
+        // 2021 day 16: a literal packet with version 6, type 4 and value 2021
+        let mut bits = BitReader::from_hex("D2FE28\n")?;
+        assert_eq!(bits.remaining(), 24);
+        assert_eq!(bits.read(3)?, 6);
+        assert_eq!(bits.read(3)?, 4);
+
+        let mut value = 0;
+        loop {
+            let more = bits.read_bool()?;
+            value = value << 4 | bits.read(4)?;
+            if !more {
+                break;
+            }
+        }
+        assert_eq!(value, 2021);
+        assert_eq!(bits.position(), 21);
+        assert!(bits.read(4).is_err());
+        assert_eq!(bits.read(3)?, 0);
+        assert!(bits.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_reader() -> Result<()> {
+        let data = [0b1011_0011, 0b1100_0000];
+        let mut bits = BitReader::new(&data);
+        bits.skip(2)?;
+        {
+            let mut sub = bits.sub_reader(7)?;
+            assert_eq!(sub.read(4)?, 0b1100);
+            assert_eq!(sub.position(), 4);
+            assert_eq!(sub.read(3)?, 0b111);
+            assert!(sub.read(1).is_err());
+        }
+        assert_eq!(bits.position(), 9);
+        assert_eq!(bits.read(7)?, 0b1000000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_hex_errors() {
+        assert!(BitReader::from_hex("12G4").is_err());
+        assert!(BitReader::new(&[0]).read(65).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use super::field_error;
+use crate::error::{Error, Result};
+use regex::{Captures, Regex};
+use std::str::FromStr;
+
+/// A tuple that can be built from a regex match, one capture group per element.
+pub trait FromCaptures: Sized {
+    /// The number of capture groups needed.
+    const GROUPS: usize;
+
+    fn from_captures(captures: &Captures) -> Result<Self>;
+}
+
+/// Parses capture group `i`, with errors pointing at its column in the whole match.
+fn group<T>(captures: &Captures, i: usize) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    let line = captures.get(0).map_or("", |m| m.as_str());
+    let text = captures
+        .get(i)
+        .ok_or_else(|| {
+            Error::parse(format!(
+                "Capture group {i} did not participate in the match"
+            ))
+        })?
+        .as_str();
+    text.parse()
+        .map_err(|e| field_error::<T>(format!("Capture group {i} ({text:?})"), line, text, e))
+}
+
+macro_rules! impl_from_captures {
+    ($len:expr; $($t:ident $i:expr),+) => {
+        impl<$($t),+> FromCaptures for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Into<Error>,)+
+        {
+            const GROUPS: usize = $len;
+
+            fn from_captures(captures: &Captures) -> Result<Self> {
+                Ok(($(group::<$t>(captures, $i)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1; A 1);
+impl_from_captures!(2; A 1, B 2);
+impl_from_captures!(3; A 1, B 2, C 3);
+impl_from_captures!(4; A 1, B 2, C 3, D 4);
+impl_from_captures!(5; A 1, B 2, C 3, D 4, E 5);
+impl_from_captures!(6; A 1, B 2, C 3, D 4, E 5, F 6);
+impl_from_captures!(7; A 1, B 2, C 3, D 4, E 5, F 6, G 7);
+impl_from_captures!(8; A 1, B 2, C 3, D 4, E 5, F 6, G 7, H 8);
+
+/// Matches `line` against `re` and parses each capture group, in order, into the corresponding element of `T`.
+pub fn capture_parse<T: FromCaptures>(re: &Regex, line: &str) -> Result<T> {
+    let groups = re.captures_len() - 1;
+    if groups != T::GROUPS {
+        return Err(Error::parse(format!(
+            "Pattern {re} has {groups} capture groups, but {} values were requested",
+            T::GROUPS
+        )));
+    }
+    let captures = re
+        .captures(line)
+        .ok_or_else(|| Error::parse(format!("{line:?} does not match {re}")))?;
+    T::from_captures(&captures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_parse() {
+        let re = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)$").unwrap();
+        let parsed: (u32, u32, char, String) = capture_parse(&re, "1-3 a: abcde").unwrap();
+        assert_eq!(parsed, (1, 3, 'a', "abcde".to_string()));
+
+        let re = Regex::new(r"^#(\d+) @ (\d+),(\d+)(?:: (\d+)x(\d+))?$").unwrap();
+        let (id,) = capture_parse::<(u32,)>(&Regex::new(r"#(\d+)").unwrap(), "#123 @ 3,2").unwrap();
+        assert_eq!(id, 123);
+
+        let err = capture_parse::<(u8, u8, u8, u8, u8)>(&re, "#1 @ 3,2").unwrap_err();
+        assert!(
+            err.to_string().contains("group 4 did not participate"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_capture_parse_errors() {
+        let re = Regex::new(r"(\w+) (\w+)").unwrap();
+
+        let err = capture_parse::<(u32,)>(&re, "1 2").unwrap_err();
+        assert!(err.to_string().contains("2 capture groups"), "{err}");
+
+        let err = capture_parse::<(u32, u32)>(&re, "nope").unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+
+        let err = capture_parse::<(u32, u8)>(&re, "12 300").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(r#"group 2 ("300") is not a valid u8"#),
+            "{err}"
+        );
+        assert!(
+            err.to_string().ends_with("at column 4:\n12 300\n   ^"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use super::{FromFields, field_error};
+use crate::error::{Error, Result};
+use std::str::FromStr;
+
+/// Splits `line` at every character in `delimiters`, ignoring empty pieces, and parses the fields into the elements
+/// of `T`. `"3-5 c: abcde"` with delimiters `"- :"` gives the fields `3`, `5`, `c` and `abcde`.
+pub fn parse_delimited<T: FromFields>(line: &str, delimiters: &str) -> Result<T> {
+    let fields = line
+        .split(|c| delimiters.contains(c))
+        .filter(|field| !field.is_empty())
+        .collect::<Vec<_>>();
+    if fields.len() != T::FIELDS {
+        return Err(Error::parse(format!(
+            "{line:?} has {} fields split at {delimiters:?}, expected {}",
+            fields.len(),
+            T::FIELDS
+        )));
+    }
+    T::from_line_fields(line, &fields)
+}
+
+/// Splits `line` at each `delimiter`, trims the pieces, and parses every one as `T`, e.g. `"1, 2,3"` with `","`.
+/// An empty line is an empty list.
+pub fn parse_list<T>(line: &str, delimiter: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    if line.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    line.split(delimiter)
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, item)| {
+            item.parse()
+                .map_err(|e| field_error::<T>(format!("Item {} ({item:?})", i + 1), line, item, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_delimited() -> Result<()> {
+        let (lo, hi, letter, password): (usize, usize, char, String) =
+            parse_delimited("3-5 c: password", "- :")?;
+        assert_eq!((lo, hi, letter, password.as_str()), (3, 5, 'c', "password"));
+
+        let (x, y, z) = parse_delimited::<(i32, i32, i32)>("1,-2,3", ",")?;
+        assert_eq!((x, y, z), (1, -2, 3));
+
+        let err = parse_delimited::<(u8, u8)>("1,2,3", ",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"1,2,3\" has 3 fields split at \",\", expected 2"
+        );
+        let err = parse_delimited::<(u8, u8)>("1-x", "-").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Field 2 (\"x\") is not a valid u8"),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_list() -> Result<()> {
+        assert_eq!(parse_list::<u32>("3,4, 3,1", ",")?, [3, 4, 3, 1]);
+        assert!(parse_list::<i64>("", ",")?.is_empty());
+        let err = parse_list::<u32>("1,,2", ",").unwrap_err();
+        assert!(err.to_string().starts_with("Item 2 (\"\")"), "{err}");
+        let err = parse_list::<u32>("1, 2, -3", ",").unwrap_err();
+        assert!(
+            err.to_string().ends_with("at column 7:\n1, 2, -3\n      ^"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::error::{Error, Result};
+
+/// Strips a leading `#` or `0x`, as colors and literals are usually written.
+fn strip_hex_prefix(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix('#')
+        .or_else(|| s.strip_prefix("0x"))
+        .unwrap_or(s)
+}
+
+pub(crate) fn hex_digit(c: char, text: &str) -> Result<u8> {
+    c.to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| Error::parse(format!("Invalid hex digit {c:?} in {text:?}")))
+}
+
+/// Parses a hex string such as `"#70c710"` into bytes, two digits per byte.
+pub fn parse_hex_bytes(s: &str) -> Result<Vec<u8>> {
+    let digits = strip_hex_prefix(s)
+        .chars()
+        .map(|c| hex_digit(c, s))
+        .collect::<Result<Vec<_>>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(Error::parse(format!(
+            "{s:?} has an odd number of hex digits"
+        )));
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect())
+}
+
+/// Parses up to 16 hex digits, with or without a `#` or `0x` prefix, as a number.
+pub fn parse_hex_u64(s: &str) -> Result<u64> {
+    u64::from_str_radix(strip_hex_prefix(s), 16)
+        .map_err(|e| Error::parse(format!("Invalid hex number {s:?}: {e}")))
+}
+
+/// Parses a `#rrggbb` color into its red, green and blue components.
+pub fn parse_rgb(s: &str) -> Result<(u8, u8, u8)> {
+    match parse_hex_bytes(s)?[..] {
+        [r, g, b] => Ok((r, g, b)),
+        _ => Err(Error::parse(format!("{s:?} is not a #rrggbb color"))),
+    }
+}
+
+/// Lowercase hex, two digits per byte, e.g. for rendering a digest.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() -> Result<()> {
+        assert_eq!(parse_hex_bytes("#70c710")?, [0x70, 0xc7, 0x10]);
+        assert_eq!(parse_hex_bytes("DEADbeef")?, [0xde, 0xad, 0xbe, 0xef]);
+        assert!(parse_hex_bytes("#70c71").is_err());
+        assert!(parse_hex_bytes("#70cg10").is_err());
+
+        assert_eq!(parse_rgb("#70c710")?, (0x70, 0xc7, 0x10));
+        assert!(parse_rgb("#70c7").is_err());
+
+        // 2023 day 18: the first five digits are the distance
+        assert_eq!(parse_hex_u64(&"#70c710"[..6])?, 461_937);
+        assert_eq!(parse_hex_u64("0xff")?, 255);
+        assert!(parse_hex_u64("").is_err());
+
+        assert_eq!(to_hex(&parse_hex_bytes("#00a0ff")?), "00a0ff");
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::{Error, Result};
+use std::any::Any;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Parses every line as `T`, with errors giving the 1-based number of the line that failed. An [`Error::Parse`] from
+/// `T` keeps its message and column, with the caret shown under the line; any other error quotes the line.
+pub fn parse_lines<S, T>(lines: &[S]) -> Result<Vec<T>>
+where
+    S: AsRef<str>,
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.as_ref();
+            line.parse().map_err(|e| line_error(e, line).at_line(i + 1))
+        })
+        .collect()
+}
+
+fn line_error(err: impl Display + 'static, line: &str) -> Error {
+    match (&err as &dyn Any).downcast_ref::<Error>() {
+        Some(Error::Parse {
+            message,
+            column: Some(column),
+            ..
+        }) => Error::parse(message.clone()).at_column(*column, line),
+        Some(Error::Parse { message, .. }) => Error::parse(message.clone()),
+        _ => Error::parse(format!("Invalid line {line:?}: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::two_dimensional::Point;
+
+    #[test]
+    fn test_parse_lines() {
+        let points: Vec<Point<i32>> = parse_lines(&["1,2", "-3,4"]).unwrap();
+        assert_eq!(points, [Point::new(1, 2), Point::new(-3, 4)]);
+
+        let err = parse_lines::<_, Point<i32>>(&["1,2", "3,4", "5,x"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid coordinate \"x\": invalid digit found in string at line 3, column 3:\n5,x\n  ^"
+        );
+
+        let err = parse_lines::<_, u8>(&["7", "256"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid line \"256\": number too large to fit in target type at line 2"
+        );
+    }
+}
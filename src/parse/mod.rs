@@ -0,0 +1,46 @@
+use crate::error::{Error, column_of};
+use std::any::type_name;
+use std::fmt::Display;
+use std::str::FromStr;
+
+mod bit_reader;
+#[cfg(feature = "regex")]
+mod captures;
+mod delimited;
+mod hex;
+mod lines;
+mod scanner;
+mod sections;
+mod template;
+
+pub use bit_reader::BitReader;
+#[cfg(feature = "regex")]
+pub use captures::{FromCaptures, capture_parse};
+pub use delimited::{parse_delimited, parse_list};
+pub use hex::{parse_hex_bytes, parse_hex_u64, parse_rgb, to_hex};
+pub use lines::parse_lines;
+pub use scanner::Scanner;
+pub use sections::{groups, split_header, split_header_fields, split_when};
+pub use template::{FromFields, parse_template, template_fields};
+
+/// The error for `part`, a slice of `line`, failing to parse as `T`. The message leads with `what`, and the caret points
+/// at `part`, or further in when `T`'s own error has a column.
+fn field_error<T>(what: impl Display, line: &str, part: &str, err: T::Err) -> Error
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    let (reason, offset) = match err.into() {
+        Error::Parse {
+            message, column, ..
+        } => (message, column.map_or(0, |c| c - 1)),
+        other => (other.to_string(), 0),
+    };
+    let column = column_of(line, part).map(|column| column + offset);
+    Error::Parse {
+        message: format!("{what} is not a valid {}: {reason}", type_name::<T>()),
+        line: None,
+        column,
+        text: column.map(|_| line.to_string()),
+    }
+}
@@ -0,0 +1,197 @@
+use crate::error::{Error, Result};
+use std::any::type_name;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A cursor over a line of input for parsing structured text piece by piece. Errors point at the column where parsing
+/// failed.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Byte offset of the cursor.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The unconsumed input.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// An [`Error::Parse`] describing what was expected at the cursor, with the line and a caret under the column.
+    pub fn error(&self, expected: impl Display) -> Error {
+        Error::Parse {
+            message: format!("Expected {expected}"),
+            line: None,
+            column: Some(self.input[..self.pos].chars().count() + 1),
+            text: Some(self.input.to_string()),
+        }
+    }
+
+    /// Consumes `literal`, or fails without consuming anything.
+    pub fn expect(&mut self, literal: &str) -> Result<()> {
+        if self.accept(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format_args!("{literal:?}")))
+        }
+    }
+
+    /// Consumes `literal` if it is next, returning whether it was.
+    pub fn accept(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    /// Consumes characters while `predicate` holds, returning them (possibly empty).
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes everything before the next `ch`, leaving `ch` itself unconsumed.
+    pub fn take_until(&mut self, ch: char) -> Result<&'a str> {
+        let len = self
+            .rest()
+            .find(ch)
+            .ok_or_else(|| self.error(format_args!("{ch:?} later in the line")))?;
+        let taken = &self.rest()[..len];
+        self.pos += len;
+        Ok(taken)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes a non-empty run of letters, digits and underscores.
+    pub fn word(&mut self) -> Result<&'a str> {
+        match self.take_while(|c| c.is_alphanumeric() || c == '_') {
+            "" => Err(self.error("a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// Consumes a single character.
+    pub fn char(&mut self) -> Result<char> {
+        let c = self.peek().ok_or_else(|| self.error("a character"))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Consumes an optionally signed integer and parses it as `T`. Fails without consuming anything.
+    pub fn int<T>(&mut self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let start = self.pos;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.pos += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let text = &self.input[start..self.pos];
+
+        let parsed = match digits {
+            "" => Err("no digits".to_string()),
+            _ => text.parse::<T>().map_err(|e| e.to_string()),
+        };
+        parsed.map_err(|e| {
+            self.pos = start;
+            self.error(format_args!("{} ({e})", type_name::<T>()))
+        })
+    }
+
+    /// Fails unless all input has been consumed.
+    pub fn finish(&self) -> Result<()> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner() {
+        let mut s = Scanner::new("Valve AA has flow rate=-13; tunnels lead to valves DD, II");
+        s.expect("Valve ").unwrap();
+        assert_eq!(s.word().unwrap(), "AA");
+        assert_eq!(s.take_until('=').unwrap(), " has flow rate");
+        s.expect("=").unwrap();
+        assert_eq!(s.int::<i32>().unwrap(), -13);
+        assert!(s.accept(";"));
+        s.skip_whitespace();
+        s.take_while(|c| c != 'v');
+        assert_eq!(s.rest(), "valves DD, II");
+        s.expect("valves ").unwrap();
+
+        let mut valves = vec![s.word().unwrap()];
+        while s.accept(", ") {
+            valves.push(s.word().unwrap());
+        }
+        assert_eq!(valves, ["DD", "II"]);
+        assert_eq!(s.peek(), None);
+        s.finish().unwrap();
+    }
+
+    #[test]
+    fn test_scanner_errors() {
+        let mut s = Scanner::new("move 3 from x");
+        let err = s.expect("mv").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected \"mv\" at column 1:\nmove 3 from x\n^"
+        );
+
+        s.expect("move ").unwrap();
+        assert_eq!(s.int::<u8>().unwrap(), 3);
+        s.expect(" from ").unwrap();
+        let err = s.int::<u8>().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Expected u8 (no digits) at column 13"),
+            "{err}"
+        );
+        assert_eq!(s.position(), 12);
+        assert_eq!(s.char().unwrap(), 'x');
+        assert!(s.char().is_err());
+        assert!(s.word().is_err());
+        assert!(s.take_until(',').is_err());
+
+        let mut s = Scanner::new("300 left");
+        assert!(s.int::<u8>().is_err());
+        assert_eq!(s.int::<u16>().unwrap(), 300);
+        assert!(
+            s.finish()
+                .unwrap_err()
+                .to_string()
+                .contains("end of line at column 4")
+        );
+    }
+}
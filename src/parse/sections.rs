@@ -0,0 +1,151 @@
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Splits `lines` into groups separated by blank lines, skipping empty groups.
+pub fn groups<S: AsRef<str>>(lines: &[S]) -> Vec<&[S]> {
+    lines
+        .split(|line| line.as_ref().trim().is_empty())
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Splits `lines` into chunks that each begin at a line where `is_start` holds, for records introduced by a header
+/// line (`Monkey 0:`, `--- scanner 1 ---`) rather than separated by blank lines. Lines before the first header form
+/// their own chunk. Blank lines at the ends of a chunk are dropped, and chunks left empty are skipped.
+pub fn split_when<S, F>(lines: &[S], mut is_start: F) -> Vec<&[S]>
+where
+    S: AsRef<str>,
+    F: FnMut(&str) -> bool,
+{
+    let mut starts = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, line)| *i == 0 || is_start(line.as_ref()))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    starts.push(lines.len());
+
+    starts
+        .windows(2)
+        .map(|w| trim_blank(&lines[w[0]..w[1]]))
+        .filter(|chunk| !chunk.is_empty())
+        .collect()
+}
+
+/// Splits `lines` at the first blank line into a header and a body, for two-part inputs such as a crate drawing then
+/// moves, or seeds then maps. Blank lines at the ends of either part are dropped; with no blank line, the body is
+/// empty.
+pub fn split_header<S: AsRef<str>>(lines: &[S]) -> (&[S], &[S]) {
+    let lines = trim_blank(lines);
+    match lines
+        .iter()
+        .position(|line| line.as_ref().trim().is_empty())
+    {
+        Some(i) => (trim_blank(&lines[..i]), trim_blank(&lines[i + 1..])),
+        None => (lines, &[]),
+    }
+}
+
+/// Like [`split_header`], but parses each header line as a `key: value` pair, trimming both.
+pub fn split_header_fields<S: AsRef<str>>(lines: &[S]) -> Result<(HashMap<&str, &str>, &[S])> {
+    let (header, body) = split_header(lines);
+    let mut fields = HashMap::new();
+    for (i, line) in header.iter().enumerate() {
+        let line = line.as_ref();
+        let (key, value) = line.split_once(':').ok_or_else(|| {
+            Error::parse(format!("Header line {line:?} is not a key: value pair")).at_line(i + 1)
+        })?;
+        if fields.insert(key.trim(), value.trim()).is_some() {
+            return Err(
+                Error::parse(format!("Duplicate header key {:?}", key.trim())).at_line(i + 1),
+            );
+        }
+    }
+    Ok((fields, body))
+}
+
+fn trim_blank<S: AsRef<str>>(mut lines: &[S]) -> &[S] {
+    while let [first, rest @ ..] = lines
+        && first.as_ref().trim().is_empty()
+    {
+        lines = rest;
+    }
+    while let [rest @ .., last] = lines
+        && last.as_ref().trim().is_empty()
+    {
+        lines = rest;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let lines = ["1000", "2000", "", "4000", "", "", "5000", "6000", ""];
+        assert_eq!(
+            groups(&lines),
+            [&["1000", "2000"][..], &["4000"], &["5000", "6000"]]
+        );
+        assert!(groups::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_split_when() {
+        let lines = [
+            "intro",
+            "Monkey 0:",
+            "  Starting items: 79, 98",
+            "",
+            "Monkey 1:",
+            "  Starting items: 54",
+            "",
+        ];
+        let monkeys = split_when(&lines, |line| line.starts_with("Monkey"));
+        assert_eq!(
+            monkeys,
+            [
+                &["intro"][..],
+                &["Monkey 0:", "  Starting items: 79, 98"],
+                &["Monkey 1:", "  Starting items: 54"]
+            ]
+        );
+
+        // Headers without any blank lines between records
+        let scanners = [
+            "--- scanner 0 ---",
+            "1,2",
+            "--- scanner 1 ---",
+            "3,4",
+            "5,6",
+        ];
+        let scanners = split_when(&scanners, |line| line.starts_with("---"));
+        assert_eq!(scanners.len(), 2);
+        assert_eq!(scanners[1], ["--- scanner 1 ---", "3,4", "5,6"]);
+    }
+
+    #[test]
+    fn test_split_header() {
+        let lines = ["    [D]", "[N] [C]", " 1   2", "", "move 1 from 2 to 1", ""];
+        let (header, body) = split_header(&lines);
+        assert_eq!(header, ["    [D]", "[N] [C]", " 1   2"]);
+        assert_eq!(body, ["move 1 from 2 to 1"]);
+
+        let (header, body) = split_header(&["a", "b"]);
+        assert_eq!((header.len(), body.len()), (2, 0));
+    }
+
+    #[test]
+    fn test_split_header_fields() -> anyhow::Result<()> {
+        let lines = ["seeds: 79 14 55 13", "", "seed-to-soil map:", "50 98 2"];
+        let (fields, body) = split_header_fields(&lines)?;
+        assert_eq!(fields["seeds"], "79 14 55 13");
+        assert_eq!(body, ["seed-to-soil map:", "50 98 2"]);
+
+        assert!(split_header_fields(&["seeds 79"]).is_err());
+        assert!(split_header_fields(&["a: 1", "a: 2"]).is_err());
+        Ok(())
+    }
+}
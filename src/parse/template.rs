@@ -0,0 +1,193 @@
+use super::field_error;
+use crate::error::{Error, Result};
+use std::str::FromStr;
+
+/// A tuple that can be parsed from the fields of a [`parse_template`] match, one field per element.
+pub trait FromFields: Sized {
+    /// The number of fields needed.
+    const FIELDS: usize;
+
+    fn from_fields(fields: &[&str]) -> Result<Self>;
+
+    /// Like [`from_fields`](Self::from_fields), with errors pointing at the failing field's column in `line`, which
+    /// the fields are slices of.
+    fn from_line_fields(_line: &str, fields: &[&str]) -> Result<Self> {
+        Self::from_fields(fields)
+    }
+}
+
+/// Parses field `i`, with errors pointing into `line` if given, or else into the field itself.
+fn field<T>(line: Option<&str>, fields: &[&str], i: usize) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    let text = fields[i];
+    text.parse().map_err(|e| {
+        let what = format!("Field {} ({text:?})", i + 1);
+        field_error::<T>(what, line.unwrap_or(text), text, e)
+    })
+}
+
+macro_rules! impl_from_fields {
+    ($len:expr; $($t:ident $i:expr),+) => {
+        impl<$($t),+> FromFields for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Into<Error>,)+
+        {
+            const FIELDS: usize = $len;
+
+            fn from_fields(fields: &[&str]) -> Result<Self> {
+                Ok(($(field::<$t>(None, fields, $i)?,)+))
+            }
+
+            fn from_line_fields(line: &str, fields: &[&str]) -> Result<Self> {
+                Ok(($(field::<$t>(Some(line), fields, $i)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; A 0);
+impl_from_fields!(2; A 0, B 1);
+impl_from_fields!(3; A 0, B 1, C 2);
+impl_from_fields!(4; A 0, B 1, C 2, D 3);
+impl_from_fields!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_fields!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_fields!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Splits `line` into the pieces matching each `{}` in `template`; everything else in the template must match
+/// literally. Each field extends to the first occurrence of the literal text that follows it, and the last field
+/// extends to the end of the line if nothing follows it.
+pub fn template_fields<'a>(line: &'a str, template: &str) -> Result<Vec<&'a str>> {
+    let mut literals = template.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let mut rest = line
+        .strip_prefix(first)
+        .ok_or_else(|| Error::parse(format!("{line:?} does not start with {first:?}")))?;
+
+    let mut fields = Vec::new();
+    for literal in literals {
+        let end = match literal {
+            "" => rest.len(),
+            _ => rest.find(literal).ok_or_else(|| {
+                Error::parse(format!(
+                    "{line:?} is missing {literal:?} after field {}",
+                    fields.len() + 1
+                ))
+            })?,
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+
+    if !rest.is_empty() {
+        return Err(Error::parse(format!(
+            "{line:?} has unexpected trailing text {rest:?}"
+        )));
+    }
+    Ok(fields)
+}
+
+/// Matches `line` against `template` and parses each `{}` field into the corresponding element of `T`. See
+/// [`parse!`](crate::parse!) for a more concise form.
+pub fn parse_template<T: FromFields>(line: &str, template: &str) -> Result<T> {
+    let placeholders = template.matches("{}").count();
+    if placeholders != T::FIELDS {
+        return Err(Error::parse(format!(
+            "Template {template:?} has {placeholders} fields, but {} values were requested",
+            T::FIELDS
+        )));
+    }
+    if template.contains("{}{}") {
+        return Err(Error::parse(format!(
+            "Template {template:?} has adjacent fields, which are ambiguous"
+        )));
+    }
+    let fields = template_fields(line, template)?;
+    T::from_line_fields(line, &fields)
+}
+
+/// Parses a line against a template with `{}` placeholders.
+///
+/// Given names, binds each field to a variable, propagating errors with `?`:
+///
+/// ```ignore
+/// parse!(line, "{} -> {}: {}", a: u32, b: Point, c: String);
+/// ```
+///
+/// Given only types, evaluates to a `Result` of the tuple of fields:
+///
+/// ```ignore
+/// let (a, b) = parse!(line, "{} -> {}", u32, u32)?;
+/// ```
+#[macro_export]
+macro_rules! parse {
+    ($line:expr, $template:expr, $($name:ident : $t:ty),+ $(,)?) => {
+        let ($($name,)+) = $crate::parse!($line, $template, $($t),+)?;
+    };
+    ($line:expr, $template:expr, $($t:ty),+ $(,)?) => {
+        $crate::parse::parse_template::<($($t,)+)>($line, $template)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::two_dimensional::Point;
+
+    #[test]
+    fn test_template_fields() {
+        assert_eq!(
+            template_fields("0,9 -> 5,9", "{} -> {}").unwrap(),
+            ["0,9", "5,9"]
+        );
+        assert_eq!(
+            template_fields("Sensor at x=2, y=18", "Sensor at x={}, y={}").unwrap(),
+            ["2", "18"]
+        );
+        assert_eq!(template_fields("abc", "abc").unwrap(), Vec::<&str>::new());
+        assert!(template_fields("0,9 => 5,9", "{} -> {}").is_err());
+        assert!(template_fields("x=1!", "x={}").is_ok());
+        assert!(template_fields("x=1!", "x={}.").is_err());
+        assert!(template_fields("[1] extra", "[{}]").is_err());
+    }
+
+    #[test]
+    fn test_parse_macro() -> Result<()> {
+        parse!("0,9 -> 5,9: vent", "{} -> {}: {}", a: Point, b: Point, kind: String);
+        assert_eq!((a, b), (Point::new(0, 9), Point::new(5, 9)));
+        assert_eq!(kind, "vent");
+
+        let (count, from, to) = parse!(
+            "move 3 from 1 to 2",
+            "move {} from {} to {}",
+            u8,
+            usize,
+            usize
+        )?;
+        assert_eq!((count, from, to), (3, 1, 2));
+
+        let err = parse!("move x from 1 to 2", "move {} from {} to {}", u8, u8, u8).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Field 1 (\"x\") is not a valid u8: invalid digit found in string at column 6:\n\
+             move x from 1 to 2\n     ^"
+        );
+
+        // a point's own error column is carried over into the line
+        let err = parse!("0,9 -> 5,y", "{} -> {}", Point, Point).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Parse {
+                column: Some(10),
+                ..
+            }
+        ));
+
+        assert!(parse!("1 2", "{} {}", u8).is_err());
+        assert!(parse!("12", "{}{}", u8, u8).is_err());
+        Ok(())
+    }
+}
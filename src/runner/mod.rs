@@ -0,0 +1,321 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, bail};
+use clap::{ArgMatches, Command, CommandFactory, FromArgMatches, Parser};
+use log::LevelFilter;
+
+pub use crate::answer::Answer;
+use crate::config::{self, Config};
+pub use crate::context::{Example, RunContext};
+use crate::input::{self, DirectorySource, InputSource, ReadOptions};
+pub use crate::solver::{Solution, Solver};
+use crate::viz::Viz;
+use crate::{context, logging, session};
+
+const INPUT_FILE: &str = "input";
+const TEST_INPUT_FILE: &str = "example";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Test,
+    Actual,
+}
+
+impl FromStr for Input {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "test" => Ok(Self::Test),
+            "actual" => Ok(Self::Actual),
+            _ => Err(anyhow!("Invalid input type: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "advent-of-code", version, author, infer_long_args = true)]
+struct Args {
+    /// increase log level from the default for the input type
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// suppress logging and print only the answers
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// input type, Test or Actual
+    #[arg(short, long, default_value = "actual")]
+    input: String,
+
+    /// only run the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// run against every example input, comparing with each example's `.answers` file
+    #[arg(long, conflicts_with = "input")]
+    all_examples: bool,
+
+    /// recompute parts whose answers are already recorded in `input.answers`
+    #[arg(long)]
+    force: bool,
+
+    /// directory containing the input files
+    #[arg(long, env = config::INPUT_DIR_ENV)]
+    input_dir: Option<PathBuf>,
+
+    /// puzzle year
+    #[arg(long, env = config::YEAR_ENV)]
+    year: Option<u16>,
+
+    /// adventofcode.com session cookie
+    #[arg(long, env = config::SESSION_ENV, hide_env_values = true)]
+    session: Option<String>,
+
+    /// animate grid simulations in the terminal
+    #[arg(long)]
+    viz: bool,
+
+    /// milliseconds to pause between animation frames
+    #[arg(long, value_name = "MS", requires = "viz")]
+    viz_delay: Option<u64>,
+}
+
+impl Args {
+    fn config(&self) -> Config {
+        Config {
+            session: self.session.clone(),
+            year: self.year,
+            input_dir: self.input_dir.clone(),
+        }
+    }
+}
+
+/// Reads the input selected on the command line.
+///
+/// Fails with `--all-examples`, which needs a [`Solver`] run through [`RunContext::run`].
+pub fn init() -> Result<Vec<String>> {
+    lines_only(Init::new().run()?)
+}
+
+pub fn init_context() -> Result<RunContext> {
+    Init::new().run()
+}
+
+/// Like [`init`], but allows registering puzzle-specific arguments on the CLI.
+pub fn init_with_args<F>(register: F) -> Result<(ArgMatches, Vec<String>)>
+where
+    F: FnOnce(Command) -> Command,
+{
+    let (matches, ctx) = Init::new().run_with_args(register)?;
+    Ok((matches, lines_only(ctx)?))
+}
+
+/// The context's lines, which are empty with `--all-examples`.
+fn lines_only(ctx: RunContext) -> Result<Vec<String>> {
+    if !ctx.examples.is_empty() {
+        bail!("--all-examples needs a Solver; run one with init_context() and RunContext::run");
+    }
+    Ok(ctx.lines)
+}
+
+pub fn init_test() -> Result<Vec<String>> {
+    Init::new().run_test()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Init {
+    read_options: ReadOptions,
+    log_filters: Vec<(String, LevelFilter)>,
+    source: Option<Arc<dyn InputSource + Send + Sync>>,
+}
+
+impl Init {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn strip_cr(mut self, strip_cr: bool) -> Self {
+        self.read_options.strip_cr = strip_cr;
+        self
+    }
+
+    pub fn trim_trailing_whitespace(mut self, trim: bool) -> Self {
+        self.read_options.trim_trailing_whitespace = trim;
+        self
+    }
+
+    pub fn drop_trailing_blank_lines(mut self, drop: bool) -> Self {
+        self.read_options.drop_trailing_blank_lines = drop;
+        self
+    }
+
+    /// Logs formatting problems in the input at debug level; see [`ReadOptions::diagnose`].
+    pub fn diagnose(mut self, diagnose: bool) -> Self {
+        self.read_options.diagnose = diagnose;
+        self
+    }
+
+    /// Enables all input normalization options.
+    pub fn normalized(mut self) -> Self {
+        self.read_options = ReadOptions {
+            diagnose: self.read_options.diagnose,
+            ..ReadOptions::normalized()
+        };
+        self
+    }
+
+    /// Reads input from `source` instead of the configured input directory.
+    pub fn source(mut self, source: impl InputSource + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Overrides the log level for a target, e.g. `("aoc_util::input", LevelFilter::Info)` to silence the trace
+    /// output of input reading. `RUST_LOG` still takes precedence.
+    pub fn log_filter(mut self, target: impl Into<String>, level: LevelFilter) -> Self {
+        self.log_filters.push((target.into(), level));
+        self
+    }
+
+    pub fn run(self) -> Result<RunContext> {
+        self.run_from_args(Args::parse())
+    }
+
+    pub fn run_with_args<F>(self, register: F) -> Result<(ArgMatches, RunContext)>
+    where
+        F: FnOnce(Command) -> Command,
+    {
+        let matches = register(Args::command()).get_matches();
+        let args = Args::from_arg_matches(&matches)?;
+        let ctx = self.run_from_args(args)?;
+        Ok((matches, ctx))
+    }
+
+    pub fn run_test(self) -> Result<Vec<String>> {
+        logging::init_test_logger()?;
+        let config = Config::load()?.merge(Config::from_env()?);
+        self.read(&config, Input::Test)
+    }
+
+    fn run_from_args(self, args: Args) -> Result<RunContext> {
+        let verbose = args.verbose;
+        // examples are test input, whatever `--input` says
+        let input = if args.all_examples {
+            Input::Test
+        } else {
+            Input::from_str(&args.input)?
+        };
+
+        let log_level = match (input, verbose) {
+            (Input::Actual, false) => LevelFilter::Info,
+            (Input::Actual, true) => LevelFilter::Debug,
+            (Input::Test, false) => LevelFilter::Debug,
+            (Input::Test, true) => LevelFilter::Trace,
+        };
+
+        // with no logger installed, nothing is logged at all
+        if !args.quiet {
+            logging::init_logger(log_level, &self.log_filters)?;
+        }
+
+        let mut config = Config::load()?.merge(args.config());
+        config.session = session::resolve(&config);
+
+        let (lines, examples) = if args.all_examples {
+            (Vec::new(), self.read_examples(&config)?)
+        } else {
+            (self.read(&config, input)?, Vec::new())
+        };
+        let recorded = if input == Input::Actual && !args.force {
+            let source = self.input_source(&config);
+            input::read_expected_answers(source.as_ref(), INPUT_FILE)?
+        } else {
+            Vec::new()
+        };
+        let mut viz = Viz::new(args.viz);
+        if let Some(ms) = args.viz_delay {
+            viz = viz.with_delay(Duration::from_millis(ms));
+        }
+
+        Ok(RunContext {
+            lines,
+            input,
+            verbose,
+            quiet: args.quiet,
+            part: args.part,
+            day: context::current_day(),
+            year: config.year,
+            config,
+            viz,
+            examples,
+            recorded,
+        })
+    }
+
+    fn read(&self, config: &Config, input: Input) -> Result<Vec<String>> {
+        let file = match input {
+            Input::Actual => INPUT_FILE,
+            Input::Test => TEST_INPUT_FILE,
+        };
+        let source = self.input_source(config);
+        Ok(input::read_input(source.as_ref(), file, self.read_options)?)
+    }
+
+    fn read_examples(&self, config: &Config) -> Result<Vec<Example>> {
+        let source = self.input_source(config);
+        let examples = input::example_names(source.as_ref())?
+            .into_iter()
+            .map(|name| {
+                Ok(Example {
+                    lines: input::read_input(source.as_ref(), &name, self.read_options)?,
+                    expected: input::read_expected_answers(source.as_ref(), &name)?,
+                    name,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if examples.is_empty() {
+            bail!("No example inputs found");
+        }
+        Ok(examples)
+    }
+
+    fn input_source(&self, config: &Config) -> Arc<dyn InputSource + Send + Sync> {
+        self.source
+            .clone()
+            .unwrap_or_else(|| Arc::new(DirectorySource::new(config.input_dir())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_only_rejects_examples() {
+        let mut ctx = RunContext {
+            lines: vec!["1".into()],
+            input: Input::Actual,
+            verbose: false,
+            quiet: false,
+            part: None,
+            day: None,
+            year: None,
+            config: Config::default(),
+            viz: Viz::new(false),
+            examples: Vec::new(),
+            recorded: Vec::new(),
+        };
+        assert_eq!(lines_only(ctx.clone()).unwrap(), ["1"]);
+
+        ctx.examples.push(Example {
+            name: "example".into(),
+            lines: vec!["2".into()],
+            expected: Vec::new(),
+        });
+        assert!(lines_only(ctx).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+use crate::collections::MinHeap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// How A* orders states whose estimated total costs are equal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Prefer the state furthest from the start, which usually reaches the goal with the fewest expansions
+    #[default]
+    HighestCost,
+    /// Prefer the state closest to the start
+    LowestCost,
+    /// Prefer the state that was discovered first
+    Insertion,
+}
+
+/// Shortest path from `start` to the first state satisfying `is_goal`, guided by `heuristic`. The heuristic must never
+/// overestimate the remaining cost for the result to be optimal. Returns the total cost and the path, including both
+/// endpoints.
+pub fn astar<S, FS, I, FH, FG>(
+    start: S,
+    successors: FS,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    astar_with_tie_break(start, successors, heuristic, is_goal, TieBreak::default())
+}
+
+/// Like [`astar`], with an explicit tie-breaking rule.
+pub fn astar_with_tie_break<S, FS, I, FH, FG>(
+    start: S,
+    successors: FS,
+    heuristic: FH,
+    is_goal: FG,
+    tie_break: TieBreak,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    let search = best_first(start, successors, heuristic, is_goal, tie_break, false)?;
+    Some((search.cost, search.path()))
+}
+
+/// Finds the optimal cost along with every state that lies on at least one optimal path to any goal. The heuristic
+/// must be consistent (never decreasing by more than the step cost) for the result to be complete; pass `|_| 0` for
+/// Dijkstra's algorithm.
+pub fn astar_all<S, FS, I, FH, FG>(
+    start: S,
+    successors: FS,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<(u64, HashSet<S>)>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    let search = best_first(
+        start,
+        successors,
+        heuristic,
+        is_goal,
+        TieBreak::default(),
+        true,
+    )?;
+    Some((search.cost, search.on_optimal_paths()))
+}
+
+/// The outcome of [`best_first`]: the optimal cost, the goal states reached at that cost, and the predecessors of
+/// every state seen.
+pub(super) struct Search<S> {
+    pub(super) cost: u64,
+    goals: Vec<usize>,
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+    /// Additional equal-cost predecessors, only tracked when searching for all optimal paths
+    other_parents: HashMap<usize, Vec<usize>>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    pub(super) fn path(&self) -> Vec<S> {
+        let mut index = self.goals[0];
+        let mut path = vec![self.states[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.states[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn on_optimal_paths(&self) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack = self.goals.clone();
+        while let Some(index) = stack.pop() {
+            if !seen.insert(index) {
+                continue;
+            }
+            stack.extend(self.parents[index]);
+            if let Some(others) = self.other_parents.get(&index) {
+                stack.extend(others);
+            }
+        }
+        seen.into_iter().map(|i| self.states[i].clone()).collect()
+    }
+}
+
+/// Best-first search ordered by cost plus `heuristic`. With a zero heuristic this is Dijkstra's algorithm. When
+/// `find_all` is set, the search continues past the first goal to record every optimal route.
+pub(super) fn best_first<S, FS, I, FH, FG>(
+    start: S,
+    mut successors: FS,
+    mut heuristic: FH,
+    mut is_goal: FG,
+    tie_break: TieBreak,
+    find_all: bool,
+) -> Option<Search<S>>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    let mut sequence = 0u64;
+    let mut tie = |cost: u64| match tie_break {
+        TieBreak::HighestCost => u64::MAX - cost,
+        TieBreak::LowestCost => cost,
+        TieBreak::Insertion => {
+            sequence += 1;
+            sequence
+        }
+    };
+
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut costs = vec![0];
+    let mut heap = MinHeap::from_iter([(heuristic(&start), tie(0), 0, 0)]);
+    let mut search = Search {
+        cost: 0,
+        goals: Vec::new(),
+        states: vec![start],
+        parents: vec![None],
+        other_parents: HashMap::new(),
+    };
+
+    while let Some((estimate, _, cost, index)) = heap.pop() {
+        if !search.goals.is_empty() && estimate > search.cost {
+            break;
+        }
+        if cost > costs[index] {
+            continue;
+        }
+
+        let state = search.states[index].clone();
+        if is_goal(&state) {
+            if search.goals.is_empty() {
+                search.cost = cost;
+            }
+            search.goals.push(index);
+            if !find_all {
+                break;
+            }
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(e) => {
+                    let i = *e.get();
+                    if next_cost > costs[i] {
+                        continue;
+                    }
+                    if next_cost == costs[i] {
+                        if find_all && search.parents[i] != Some(index) {
+                            search.other_parents.entry(i).or_default().push(index);
+                        }
+                        continue;
+                    }
+                    costs[i] = next_cost;
+                    search.parents[i] = Some(index);
+                    search.other_parents.remove(&i);
+                    i
+                }
+                Entry::Vacant(e) => {
+                    let i = search.states.len();
+                    search.states.push(e.key().clone());
+                    search.parents.push(Some(index));
+                    costs.push(next_cost);
+                    e.insert(i);
+                    i
+                }
+            };
+
+            let estimate = next_cost + heuristic(&search.states[next_index]);
+            heap.push((estimate, tie(next_cost), next_cost, next_index));
+        }
+    }
+
+    (!search.goals.is_empty()).then_some(search)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::two_dimensional::Point;
+
+    fn open_grid_successors(size: usize) -> impl FnMut(&Point) -> Vec<(Point, u64)> {
+        move |p: &Point| {
+            let mut next = Vec::new();
+            if p.x > 0 {
+                next.push((Point::new(p.x - 1, p.y), 1));
+            }
+            if p.y > 0 {
+                next.push((Point::new(p.x, p.y - 1), 1));
+            }
+            if p.x + 1 < size {
+                next.push((Point::new(p.x + 1, p.y), 1));
+            }
+            if p.y + 1 < size {
+                next.push((Point::new(p.x, p.y + 1), 1));
+            }
+            next
+        }
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = Point::new(9, 9);
+        for tie_break in [
+            TieBreak::HighestCost,
+            TieBreak::LowestCost,
+            TieBreak::Insertion,
+        ] {
+            let (cost, path) = astar_with_tie_break(
+                Point::new(0, 0),
+                open_grid_successors(10),
+                |p| p.manhattan_distance(&goal) as u64,
+                |&p| p == goal,
+                tie_break,
+            )
+            .unwrap();
+            assert_eq!(cost, 18);
+            assert_eq!(path.len(), 19);
+            assert_eq!(path.first(), Some(&Point::new(0, 0)));
+            assert_eq!(path.last(), Some(&goal));
+        }
+
+        assert_eq!(
+            astar(
+                Point::new(0, 0),
+                open_grid_successors(10),
+                |_| 0,
+                |p| p.x > 20
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_astar_all() {
+        let goal = Point::new(2, 2);
+        let (cost, states) = astar_all(
+            Point::new(0, 0),
+            open_grid_successors(5),
+            |p| p.manhattan_distance(&goal) as u64,
+            |&p| p == goal,
+        )
+        .unwrap();
+
+        // Every monotone path from (0, 0) to (2, 2) is optimal, covering the 3x3 square
+        assert_eq!(cost, 4);
+        assert_eq!(states.len(), 9);
+        assert!(states.iter().all(|p| p.x <= 2 && p.y <= 2));
+    }
+
+    #[test]
+    fn test_astar_all_multiple_goals() {
+        // Two routes of cost 2 to different goal states, and one longer route
+        let edges = HashMap::from([
+            ("s", vec![("a", 1), ("b", 1), ("c", 5)]),
+            ("a", vec![("g1", 1)]),
+            ("b", vec![("g2", 1)]),
+            ("c", vec![("g3", 1)]),
+        ]);
+        let (cost, states) = astar_all(
+            "s",
+            |n| edges.get(n).cloned().unwrap_or_default(),
+            |_| 0,
+            |n| n.starts_with('g'),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 2);
+        assert_eq!(states, HashSet::from(["s", "a", "b", "g1", "g2"]));
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BinaryHeap;
+
+struct Entry<S, V> {
+    bound: V,
+    state: S,
+}
+
+impl<S, V: Ord> PartialEq for Entry<S, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bound == other.bound
+    }
+}
+
+impl<S, V: Ord> Eq for Entry<S, V> {}
+
+impl<S, V: Ord> PartialOrd for Entry<S, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, V: Ord> Ord for Entry<S, V> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.bound.cmp(&other.bound)
+    }
+}
+
+/// Best-first branch and bound, maximizing `value`. Wrap values in [`std::cmp::Reverse`] to minimize instead.
+///
+/// * `expand` yields a state's children.
+/// * `value` is what a state is worth if the search stopped there, e.g. pressure already committed.
+/// * `bound` is an optimistic estimate of the best value any descendant of a state could reach. It must never
+///   underestimate, or the optimum may be pruned.
+///
+/// States are explored in order of decreasing bound, and any state whose bound can't beat the best value found so far
+/// (the incumbent) is discarded. Returns the incumbent and its state.
+pub fn branch_and_bound<S, V, I, FE, FV, FB>(
+    start: S,
+    mut expand: FE,
+    mut value: FV,
+    mut bound: FB,
+) -> (V, S)
+where
+    S: Clone,
+    V: Ord + Copy,
+    FE: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    FV: FnMut(&S) -> V,
+    FB: FnMut(&S) -> V,
+{
+    let mut best = (value(&start), start.clone());
+    let mut heap = BinaryHeap::from([Entry {
+        bound: bound(&start),
+        state: start,
+    }]);
+
+    while let Some(Entry { bound: b, state }) = heap.pop() {
+        if b <= best.0 {
+            // Every remaining state has a bound at most this one's
+            break;
+        }
+
+        for child in expand(&state) {
+            let v = value(&child);
+            if v > best.0 {
+                best = (v, child.clone());
+            }
+            let b = bound(&child);
+            if b > best.0 {
+                heap.push(Entry {
+                    bound: b,
+                    state: child,
+                });
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+
+    /// 0/1 knapsack: (next item, weight used, value so far)
+    type Knapsack = (usize, u64, u64);
+
+    const ITEMS: [(u64, u64); 6] = [(12, 4), (2, 2), (1, 1), (4, 10), (1, 2), (8, 9)];
+    const CAPACITY: u64 = 15;
+
+    fn expand(&(i, weight, value): &Knapsack) -> Vec<Knapsack> {
+        let Some(&(w, v)) = ITEMS.get(i) else {
+            return vec![];
+        };
+        let mut children = vec![(i + 1, weight, value)];
+        if weight + w <= CAPACITY {
+            children.push((i + 1, weight + w, value + v));
+        }
+        children
+    }
+
+    #[test]
+    fn test_branch_and_bound_maximize() {
+        let mut expanded = 0;
+        let (best, (_, weight, _)) = branch_and_bound(
+            (0, 0, 0),
+            |s| {
+                expanded += 1;
+                expand(s)
+            },
+            |&(_, _, value)| value,
+            // Optimistic: take every remaining item regardless of weight
+            |&(i, _, value)| {
+                value
+                    + ITEMS[i.min(ITEMS.len())..]
+                        .iter()
+                        .map(|(_, v)| v)
+                        .sum::<u64>()
+            },
+        );
+
+        assert_eq!(best, 23);
+        assert!(weight <= CAPACITY);
+        assert!(expanded < 2usize.pow(ITEMS.len() as u32));
+    }
+
+    #[test]
+    fn test_branch_and_bound_minimize() {
+        // Fewest coins making 11 from {1, 5, 6, 9}: (remaining, coins used)
+        let coins = [1u32, 5, 6, 9];
+        let (Reverse(best), state) = branch_and_bound(
+            (11u32, 0u32),
+            |&(remaining, used)| {
+                coins
+                    .iter()
+                    .filter(move |&&c| c <= remaining)
+                    .map(move |&c| (remaining - c, used + 1))
+                    .collect::<Vec<_>>()
+            },
+            |&(remaining, used)| Reverse(if remaining == 0 { used } else { u32::MAX }),
+            |&(remaining, used)| Reverse(used + remaining.div_ceil(9)),
+        );
+
+        assert_eq!(best, 2);
+        assert_eq!(state, (0, 2));
+    }
+}
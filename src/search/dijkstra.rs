@@ -0,0 +1,137 @@
+use super::astar::{TieBreak, best_first};
+use std::hash::Hash;
+
+/// Shortest path from `start` to the first state satisfying `is_goal`, where `successors` yields each neighboring
+/// state along with the cost of moving to it. Returns the total cost and the path, including both endpoints.
+pub fn dijkstra<S, FS, I, FG>(start: S, successors: FS, is_goal: FG) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FG: FnMut(&S) -> bool,
+{
+    let search = best_first(
+        start,
+        successors,
+        |_| 0,
+        is_goal,
+        TieBreak::default(),
+        false,
+    )?;
+    Some((search.cost, search.path()))
+}
+
+/// Like [`dijkstra`], but only returns the cost, skipping path reconstruction.
+pub fn dijkstra_cost<S, FS, I, FG>(start: S, successors: FS, is_goal: FG) -> Option<u64>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FG: FnMut(&S) -> bool,
+{
+    best_first(
+        start,
+        successors,
+        |_| 0,
+        is_goal,
+        TieBreak::default(),
+        false,
+    )
+    .map(|s| s.cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Direction, Grid, neighbor_in_direction};
+    use crate::math::two_dimensional::Point;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_dijkstra_simple_graph() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('a', 7), ('c', 10), ('d', 15)]),
+            ('c', vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)]),
+            ('d', vec![('b', 15), ('c', 11), ('e', 6)]),
+            ('e', vec![('d', 6), ('f', 9)]),
+            ('f', vec![('a', 14), ('c', 2), ('e', 9)]),
+        ]);
+
+        let (cost, path) = dijkstra('a', |n| edges[n].clone(), |&n| n == 'e').unwrap();
+        assert_eq!(cost, 20);
+        assert_eq!(path, vec!['a', 'c', 'f', 'e']);
+
+        assert_eq!(
+            dijkstra_cost('a', |n| edges[n].clone(), |&n| n == 'a'),
+            Some(0)
+        );
+        assert_eq!(
+            dijkstra_cost('a', |n| edges[n].clone(), |&n| n == 'z'),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_crucible_state() -> anyhow::Result<()> {
+        // Moving costs the digit of the destination cell; at most 3 steps in a straight line before turning.
+        let grid = Grid::try_from(
+            [
+                "2413432311323",
+                "3215453535623",
+                "3255245654254",
+                "3446585845452",
+                "4546657867536",
+                "1438598798454",
+                "4457876987766",
+                "3637877979653",
+                "4654967986887",
+                "4564679986453",
+                "1224686865563",
+                "2546548887735",
+                "4322674655533",
+            ]
+            .iter()
+            .map(|row| row.bytes().map(|b| (b - b'0') as u64).collect())
+            .collect::<Vec<Vec<_>>>(),
+        )?;
+        let goal = Point::new(grid.width() - 1, grid.height() - 1);
+
+        type State = (Point, Option<Direction>, u8);
+        let grid = &grid;
+        let successors = |&(position, direction, run): &State| {
+            [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .into_iter()
+            .filter(move |&d| {
+                let reverse = matches!(
+                    (direction, d),
+                    (Some(Direction::Up), Direction::Down)
+                        | (Some(Direction::Down), Direction::Up)
+                        | (Some(Direction::Left), Direction::Right)
+                        | (Some(Direction::Right), Direction::Left)
+                );
+                !(reverse || Some(d) == direction && run == 3)
+            })
+            .filter_map(move |d| {
+                let next = neighbor_in_direction(grid, d, position)?;
+                let run = if Some(d) == direction { run + 1 } else { 1 };
+                Some(((next.position, Some(d), run), grid[next.position]))
+            })
+            .collect::<Vec<_>>()
+        };
+
+        let (cost, path) =
+            dijkstra((Point::new(0, 0), None, 0), successors, |s| s.0 == goal).unwrap();
+        assert_eq!(cost, 102);
+        assert_eq!(path.first().unwrap().0, Point::new(0, 0));
+        assert_eq!(path.last().unwrap().0, goal);
+        assert!(path.iter().all(|&(_, _, run)| run <= 3));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+struct Search<S, FS, FH, FG> {
+    successors: FS,
+    heuristic: FH,
+    is_goal: FG,
+    path: Vec<S>,
+    on_path: HashSet<S>,
+}
+
+impl<S, FS, I, FH, FG> Search<S, FS, FH, FG>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    /// Returns `Ok(cost)` when a goal is found, or `Err` with the smallest estimate that exceeded `bound`, if any.
+    fn deepen(&mut self, cost: u64, bound: u64) -> Result<u64, Option<u64>> {
+        let state = self
+            .path
+            .last()
+            .expect("path starts with the start state")
+            .clone();
+        let estimate = cost + (self.heuristic)(&state);
+        if estimate > bound {
+            return Err(Some(estimate));
+        }
+        if (self.is_goal)(&state) {
+            return Ok(cost);
+        }
+
+        let mut next_bound: Option<u64> = None;
+        let successors: Vec<_> = (self.successors)(&state).into_iter().collect();
+        for (next, step) in successors {
+            if !self.on_path.insert(next.clone()) {
+                continue;
+            }
+            self.path.push(next);
+
+            match self.deepen(cost + step, bound) {
+                Ok(total) => return Ok(total),
+                Err(Some(b)) => next_bound = Some(next_bound.map_or(b, |n| n.min(b))),
+                Err(None) => {}
+            }
+
+            let next = self.path.pop().expect("pushed above");
+            self.on_path.remove(&next);
+        }
+        Err(next_bound)
+    }
+}
+
+/// Iterative deepening A*: repeated depth-first searches with a rising bound on cost plus `heuristic`. Memory use is
+/// proportional to the path length rather than the number of states, so it suits huge state spaces with a good
+/// heuristic, at the cost of re-expanding states. Only states on the current path are checked for repeats.
+///
+/// The heuristic must never overestimate the remaining cost for the result to be optimal. Returns the total cost and
+/// the path, including both endpoints.
+pub fn ida_star<S, FS, I, FH, FG>(
+    start: S,
+    successors: FS,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    let mut search = Search {
+        successors,
+        heuristic,
+        is_goal,
+        path: vec![start.clone()],
+        on_path: HashSet::from([start.clone()]),
+    };
+
+    let mut bound = (search.heuristic)(&start);
+    loop {
+        match search.deepen(0, bound) {
+            Ok(cost) => return Some((cost, search.path)),
+            Err(Some(next)) => bound = next,
+            Err(None) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ida_star() {
+        // a short scramble of the 15-puzzle
+        let goal: Vec<u8> = (1..16).chain([0]).collect();
+        let start = vec![1, 2, 3, 4, 5, 6, 0, 8, 9, 10, 7, 11, 13, 14, 15, 12];
+
+        let successors = |board: &Vec<u8>| {
+            let blank = board.iter().position(|&t| t == 0).unwrap();
+            let (x, y) = (blank % 4, blank / 4);
+            [
+                (x > 0).then(|| blank - 1),
+                (x < 3).then(|| blank + 1),
+                (y > 0).then(|| blank - 4),
+                (y < 3).then(|| blank + 4),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|target| {
+                let mut next = board.clone();
+                next.swap(blank, target);
+                (next, 1)
+            })
+            .collect::<Vec<_>>()
+        };
+        let manhattan = |board: &Vec<u8>| {
+            board
+                .iter()
+                .enumerate()
+                .filter(|&(_, &t)| t != 0)
+                .map(|(i, &t)| {
+                    let g = t as usize - 1;
+                    ((i % 4).abs_diff(g % 4) + (i / 4).abs_diff(g / 4)) as u64
+                })
+                .sum()
+        };
+
+        let (cost, path) = ida_star(start.clone(), successors, manhattan, |b| *b == goal).unwrap();
+        assert_eq!(cost, 3);
+        assert_eq!(path.len(), 4);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn test_ida_star_weighted() {
+        let edges = [
+            vec![(1, 7), (2, 9), (5, 14)],
+            vec![(0, 7), (2, 10), (3, 15)],
+            vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            vec![(1, 15), (2, 11), (4, 6)],
+            vec![(3, 6), (5, 9)],
+            vec![(0, 14), (2, 2), (4, 9)],
+        ];
+        let successors = |&n: &usize| edges[n].clone();
+
+        assert_eq!(
+            ida_star(0, successors, |_| 0, |&n| n == 4),
+            Some((20, vec![0, 2, 5, 4]))
+        );
+        assert_eq!(ida_star(0, successors, |_| 0, |&n| n == 9), None);
+    }
+}
@@ -0,0 +1,207 @@
+mod astar;
+mod branch_and_bound;
+mod dijkstra;
+mod ida_star;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod puzzle;
+mod runs;
+
+pub use astar::{TieBreak, astar, astar_all, astar_with_tie_break};
+pub use branch_and_bound::branch_and_bound;
+pub use dijkstra::{dijkstra, dijkstra_cost};
+pub use ida_star::ida_star;
+#[cfg(feature = "rayon")]
+pub use parallel::{SearchInt, par_bfs, par_dijkstra_cost, par_find_first, par_reachable};
+pub use puzzle::{Puzzle, solve_astar, solve_bfs, solve_ida_star};
+pub use runs::{RunLimits, dijkstra_with_runs};
+
+/// What a depth-first search should do after entering a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Explore the state's successors
+    Continue,
+    /// Skip the state's successors, but keep searching elsewhere
+    Prune,
+    /// End the search immediately
+    Stop,
+}
+
+/// Callbacks for [`dfs`]. Any `FnMut(&S) -> Control` closure is a visitor with a no-op `exit`.
+pub trait Visitor<S> {
+    /// Called before the state's successors are explored.
+    fn enter(&mut self, state: &S) -> Control;
+
+    /// Called after the state's successors have been explored. Not called for states `enter` pruned.
+    fn exit(&mut self, _state: &S) {}
+}
+
+impl<S, F> Visitor<S> for F
+where
+    F: FnMut(&S) -> Control,
+{
+    fn enter(&mut self, state: &S) -> Control {
+        self(state)
+    }
+}
+
+/// Depth-first search from `start`.
+///
+/// States are not de-duplicated, so every path is explored unless the visitor prunes it; this makes it suitable for
+/// path counting when paired with on-path bookkeeping in the visitor. The search uses an explicit stack, so deep
+/// searches cannot overflow the call stack.
+///
+/// Returns the state the visitor stopped the search on, if any. `exit` is not called for states still on the stack
+/// when the search is stopped.
+pub fn dfs<S, I, FS, V>(start: S, mut successors: FS, visitor: &mut V) -> Option<S>
+where
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    V: Visitor<S>,
+{
+    match visitor.enter(&start) {
+        Control::Continue => (),
+        Control::Prune => return None,
+        Control::Stop => return Some(start),
+    }
+
+    let children = successors(&start).into_iter();
+    let mut stack = vec![(start, children)];
+
+    while let Some((_, children)) = stack.last_mut() {
+        match children.next() {
+            Some(next) => match visitor.enter(&next) {
+                Control::Continue => {
+                    let children = successors(&next).into_iter();
+                    stack.push((next, children));
+                }
+                Control::Prune => (),
+                Control::Stop => return Some(next),
+            },
+            None => {
+                if let Some((state, _)) = stack.pop() {
+                    visitor.exit(&state);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    fn graph() -> HashMap<&'static str, Vec<&'static str>> {
+        HashMap::from([
+            ("start", vec!["a", "b"]),
+            ("a", vec!["b", "c", "end"]),
+            ("b", vec!["a", "end"]),
+            ("c", vec!["a"]),
+            ("end", vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_dfs_count_paths() {
+        struct PathCounter {
+            on_path: HashSet<&'static str>,
+            paths: usize,
+        }
+
+        impl Visitor<&'static str> for PathCounter {
+            fn enter(&mut self, node: &&'static str) -> Control {
+                if !self.on_path.insert(node) {
+                    return Control::Prune;
+                }
+                if *node == "end" {
+                    self.paths += 1;
+                }
+                Control::Continue
+            }
+
+            fn exit(&mut self, node: &&'static str) {
+                self.on_path.remove(node);
+            }
+        }
+
+        let graph = graph();
+        let mut counter = PathCounter {
+            on_path: HashSet::new(),
+            paths: 0,
+        };
+
+        let stopped = dfs("start", |node| graph[node].clone(), &mut counter);
+
+        assert_eq!(stopped, None);
+        // start-a-end, start-a-b-end, start-b-end, start-b-a-end
+        assert_eq!(counter.paths, 4);
+        assert!(counter.on_path.is_empty());
+    }
+
+    #[test]
+    fn test_dfs_enter_exit_order() {
+        struct Recorder(Vec<String>);
+
+        impl Visitor<u32> for Recorder {
+            fn enter(&mut self, n: &u32) -> Control {
+                self.0.push(format!("+{n}"));
+                Control::Continue
+            }
+
+            fn exit(&mut self, n: &u32) {
+                self.0.push(format!("-{n}"));
+            }
+        }
+
+        let mut recorder = Recorder(Vec::new());
+        dfs(
+            1u32,
+            |&n| {
+                if n < 4 {
+                    vec![n * 2, n * 2 + 1]
+                } else {
+                    vec![]
+                }
+            },
+            &mut recorder,
+        );
+
+        assert_eq!(
+            recorder.0.join(" "),
+            "+1 +2 +4 -4 +5 -5 -2 +3 +6 -6 +7 -7 -3 -1"
+        );
+    }
+
+    #[test]
+    fn test_dfs_early_termination() {
+        let mut entered = 0;
+        let found = dfs(0u64, |&n| [n + 1, n + 2], &mut |&n: &u64| {
+            entered += 1;
+            if n == 10 {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        });
+
+        assert_eq!(found, Some(10));
+        assert_eq!(entered, 11);
+    }
+
+    #[test]
+    fn test_dfs_deep() {
+        let mut deepest = 0;
+        dfs(
+            0u32,
+            |&n| (n < 1_000_000).then_some(n + 1),
+            &mut |&n: &u32| {
+                deepest = deepest.max(n);
+                Control::Continue
+            },
+        );
+        assert_eq!(deepest, 1_000_000);
+    }
+}
@@ -0,0 +1,261 @@
+use rayon::prelude::*;
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// Number of candidates searched in parallel before checking for a match.
+const CHUNK: u64 = 1 << 16;
+
+/// Number of independently locked shards in a [`Sharded`] map.
+const SHARDS: usize = 64;
+
+/// A map split into separately locked shards by key hash, so threads expanding different states rarely contend.
+struct Sharded<K, V> {
+    hasher: RandomState,
+    shards: Vec<Mutex<HashMap<K, V>>>,
+}
+
+impl<K: Eq + Hash, V: Copy> Sharded<K, V> {
+    fn new() -> Self {
+        Self {
+            hasher: RandomState::new(),
+            shards: (0..SHARDS).map(|_| Mutex::default()).collect(),
+        }
+    }
+
+    fn shard(&self, key: &K) -> &Mutex<HashMap<K, V>> {
+        &self.shards[self.hasher.hash_one(key) as usize % SHARDS]
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.shard(key).lock().unwrap().get(key).copied()
+    }
+
+    /// Stores `value` for `key` if `better(value, current)` holds or `key` is new, returning whether it was stored.
+    fn update(&self, key: K, value: V, better: impl FnOnce(V, V) -> bool) -> bool {
+        match self.shard(&key).lock().unwrap().entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                true
+            }
+            Entry::Occupied(mut entry) if better(value, *entry.get()) => {
+                entry.insert(value);
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
+}
+
+/// Integer types [`par_find_first`] can search.
+pub trait SearchInt: Copy + Send + Sync {
+    /// The number of values in `start..end`.
+    fn distance(start: Self, end: Self) -> u64;
+
+    /// The value `n` steps after `self`.
+    fn offset(self, n: u64) -> Self;
+}
+
+macro_rules! impl_search_int {
+    ($($t:ty),*) => {$(
+        impl SearchInt for $t {
+            fn distance(start: Self, end: Self) -> u64 {
+                (end as i128 - start as i128).max(0) as u64
+            }
+
+            fn offset(self, n: u64) -> Self {
+                (self as i128 + n as i128) as Self
+            }
+        }
+    )*};
+}
+
+impl_search_int!(i32, i64, isize, u32, u64, usize);
+
+/// The smallest value in `range` satisfying `predicate`, searched in chunks across threads. The result is the same as
+/// a sequential search, and no chunk past the one holding the answer is searched, so `0..u64::MAX` is a fine range
+/// when there's no known upper bound.
+pub fn par_find_first<T, F>(range: Range<T>, predicate: F) -> Option<T>
+where
+    T: SearchInt,
+    F: Fn(T) -> bool + Sync,
+{
+    let len = T::distance(range.start, range.end);
+    (0..len).step_by(CHUNK as usize).find_map(|chunk| {
+        (chunk..len.min(chunk + CHUNK))
+            .into_par_iter()
+            .map(|i| range.start.offset(i))
+            .find_first(|&n| predicate(n))
+    })
+}
+
+/// Expands `start` breadth-first one level at a time, with each level's states split across threads and the visited
+/// set sharded by hash. Calls `visit(state)` on every state of each level until it returns `true`, returning that
+/// level's depth as `Ok`, and returns the number of states reached as `Err` if it never does.
+fn par_levels<S, FS, I, FV>(start: S, successors: FS, visit: FV) -> Result<usize, usize>
+where
+    S: Clone + Eq + Hash + Send + Sync,
+    FS: Fn(&S) -> I + Sync,
+    I: IntoIterator<Item = S>,
+    FV: Fn(&S) -> bool + Sync,
+{
+    let visited = Sharded::new();
+    visited.update(start.clone(), (), |_, _| false);
+
+    let mut frontier = vec![start];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        if frontier.par_iter().any(&visit) {
+            return Ok(depth);
+        }
+        frontier = frontier
+            .par_iter()
+            .flat_map_iter(&successors)
+            .filter(|next| visited.update(next.clone(), (), |_, _| false))
+            .collect();
+        depth += 1;
+    }
+    Err(visited.len())
+}
+
+/// Breadth-first search for huge state spaces with cheap transitions: the fewest steps from `start` to a state
+/// satisfying `is_goal`. Each level is expanded across threads, so this only pays off when levels
+/// are wide.
+pub fn par_bfs<S, FS, I, FG>(start: S, successors: FS, is_goal: FG) -> Option<usize>
+where
+    S: Clone + Eq + Hash + Send + Sync,
+    FS: Fn(&S) -> I + Sync,
+    I: IntoIterator<Item = S>,
+    FG: Fn(&S) -> bool + Sync,
+{
+    par_levels(start, successors, is_goal).ok()
+}
+
+/// The number of distinct states reachable from `start`, including `start` itself, explored level by level across
+/// threads.
+pub fn par_reachable<S, FS, I>(start: S, successors: FS) -> usize
+where
+    S: Clone + Eq + Hash + Send + Sync,
+    FS: Fn(&S) -> I + Sync,
+    I: IntoIterator<Item = S>,
+{
+    par_levels(start, successors, |_| false).unwrap_err()
+}
+
+/// Parallel counterpart of [`dijkstra_cost`](super::dijkstra_cost): the lowest total cost from `start` to a state
+/// satisfying `is_goal`. States are settled in buckets of equal cost, each bucket expanded across threads, so this
+/// suits small integer step costs where buckets are wide.
+pub fn par_dijkstra_cost<S, FS, I, FG>(start: S, successors: FS, is_goal: FG) -> Option<u64>
+where
+    S: Clone + Eq + Hash + Send + Sync,
+    FS: Fn(&S) -> I + Sync,
+    I: IntoIterator<Item = (S, u64)>,
+    FG: Fn(&S) -> bool + Sync,
+{
+    let best = Sharded::new();
+    let settled = Sharded::new();
+    best.update(start.clone(), 0, |_, _| false);
+
+    let mut buckets = BTreeMap::from([(0, vec![start])]);
+    while let Some((cost, states)) = buckets.pop_first() {
+        let states: Vec<S> = states
+            .into_par_iter()
+            .filter(|state| {
+                best.get(state) == Some(cost) && settled.update(state.clone(), (), |_, _| false)
+            })
+            .collect();
+        if states.par_iter().any(&is_goal) {
+            return Some(cost);
+        }
+
+        let relaxed: Vec<(u64, S)> = states
+            .par_iter()
+            .flat_map_iter(|state| {
+                successors(state)
+                    .into_iter()
+                    .map(|(next, step)| (cost + step, next))
+            })
+            .filter(|(next_cost, next)| best.update(next.clone(), *next_cost, |new, old| new < old))
+            .collect();
+        for (next_cost, next) in relaxed {
+            buckets.entry(next_cost).or_default().push(next);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_find_first() {
+        // crosses several chunks, with many later matches
+        assert_eq!(
+            par_find_first(0..u64::MAX, |n| n > 200_000 && n % 7 == 3),
+            Some(200_007)
+        );
+        assert_eq!(par_find_first(-10i64..10, |n| n * n == 49), Some(-7));
+        assert_eq!(par_find_first(5usize..5, |_| true), None);
+        assert_eq!(par_find_first(0u32..1000, |n| n > 1000), None);
+    }
+
+    fn grid_successors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..50).contains(&x) && (0..50).contains(&y) && !(x == 25 && y < 40))
+            .collect()
+    }
+
+    #[test]
+    fn test_par_bfs() {
+        let goal = (49, 0);
+        let unit = |s: &(i32, i32)| grid_successors(s).into_iter().map(|next| (next, 1));
+        let expected = crate::search::dijkstra_cost((0, 0), unit, |&s| s == goal);
+        assert_eq!(
+            par_bfs((0, 0), grid_successors, |&s| s == goal),
+            expected.map(|c| c as usize)
+        );
+        assert_eq!(par_bfs((0, 0), grid_successors, |&s| s == (0, 0)), Some(0));
+        assert_eq!(par_bfs((0, 0), grid_successors, |&s| s == (25, 0)), None);
+    }
+
+    #[test]
+    fn test_par_reachable() {
+        assert_eq!(par_reachable((0, 0), grid_successors), 50 * 50 - 40);
+        assert_eq!(par_reachable(0u32, |_| None), 1);
+    }
+
+    #[test]
+    fn test_par_dijkstra_cost() {
+        // moving right is free, so ties and zero-cost edges land in the current bucket
+        let weighted = |s: &(i32, i32)| {
+            let from = *s;
+            grid_successors(s).into_iter().map(move |next| {
+                (
+                    next,
+                    if next.0 > from.0 {
+                        0
+                    } else {
+                        1 + (next.1 % 3) as u64
+                    },
+                )
+            })
+        };
+        let goal = |&s: &(i32, i32)| s == (49, 10);
+        assert_eq!(
+            par_dijkstra_cost((0, 0), weighted, goal),
+            crate::search::dijkstra_cost((0, 0), weighted, goal)
+        );
+        assert_eq!(par_dijkstra_cost((0, 0), weighted, |&s| s == (25, 0)), None);
+    }
+}
@@ -0,0 +1,209 @@
+use super::{astar, ida_star};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A puzzle state that knows its own legal moves, for use with [`solve_bfs`], [`solve_astar`] and
+/// [`solve_ida_star`].
+pub trait Puzzle: Clone + Eq + Hash {
+    type Move: Clone;
+
+    /// Every move legal from this state.
+    fn moves(&self) -> Vec<Self::Move>;
+
+    fn apply(&mut self, mv: &Self::Move);
+
+    fn is_goal(&self) -> bool;
+
+    /// The cost of making `mv` from this state.
+    fn cost(&self, _mv: &Self::Move) -> u64 {
+        1
+    }
+
+    /// A lower bound on the cost to reach a goal, used by the informed solvers.
+    fn heuristic(&self) -> u64 {
+        0
+    }
+
+    /// The state after making `mv`.
+    fn after(&self, mv: &Self::Move) -> Self {
+        let mut next = self.clone();
+        next.apply(mv);
+        next
+    }
+}
+
+/// The fewest moves from `start` to a goal, ignoring move costs.
+pub fn solve_bfs<P: Puzzle>(start: &P) -> Option<Vec<P::Move>> {
+    let mut parents: HashMap<P, Option<(P, P::Move)>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start.clone()]);
+
+    while let Some(state) = queue.pop_front() {
+        if state.is_goal() {
+            let mut moves = Vec::new();
+            let mut current = &state;
+            while let Some((parent, mv)) = &parents[current] {
+                moves.push(mv.clone());
+                current = parent;
+            }
+            moves.reverse();
+            return Some(moves);
+        }
+
+        for mv in state.moves() {
+            if let Entry::Vacant(e) = parents.entry(state.after(&mv)) {
+                queue.push_back(e.key().clone());
+                e.insert(Some((state.clone(), mv)));
+            }
+        }
+    }
+    None
+}
+
+fn successors<P: Puzzle>(state: &P) -> Vec<(P, u64)> {
+    state
+        .moves()
+        .into_iter()
+        .map(|mv| (state.after(&mv), state.cost(&mv)))
+        .collect()
+}
+
+/// Recovers the moves between consecutive states of a path, picking the cheapest where several moves lead to the
+/// same state.
+fn moves_along<P: Puzzle>(path: &[P]) -> Vec<P::Move> {
+    path.windows(2)
+        .map(|pair| {
+            let (from, to) = (&pair[0], &pair[1]);
+            from.moves()
+                .into_iter()
+                .filter(|mv| from.after(mv) == *to)
+                .min_by_key(|mv| from.cost(mv))
+                .expect("path steps come from legal moves")
+        })
+        .collect()
+}
+
+/// The cheapest sequence of moves from `start` to a goal, guided by [`Puzzle::heuristic`]. Returns the total cost and
+/// the moves.
+pub fn solve_astar<P: Puzzle>(start: &P) -> Option<(u64, Vec<P::Move>)> {
+    let (cost, path) = astar(start.clone(), successors, P::heuristic, P::is_goal)?;
+    Some((cost, moves_along(&path)))
+}
+
+/// Like [`solve_astar`], but searching with [`ida_star`], so memory use is proportional to the solution length rather
+/// than the number of states seen.
+pub fn solve_ida_star<P: Puzzle>(start: &P) -> Option<(u64, Vec<P::Move>)> {
+    let (cost, path) = ida_star(start.clone(), successors, P::heuristic, P::is_goal)?;
+    Some((cost, moves_along(&path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The 8-puzzle, with 0 as the blank.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Tiles([u8; 9]);
+
+    impl Tiles {
+        fn blank(&self) -> usize {
+            self.0.iter().position(|&t| t == 0).unwrap()
+        }
+    }
+
+    impl Puzzle for Tiles {
+        /// The index the blank moves to
+        type Move = usize;
+
+        fn moves(&self) -> Vec<usize> {
+            let blank = self.blank();
+            let (x, y) = (blank % 3, blank / 3);
+            let mut moves = Vec::new();
+            if x > 0 {
+                moves.push(blank - 1);
+            }
+            if x < 2 {
+                moves.push(blank + 1);
+            }
+            if y > 0 {
+                moves.push(blank - 3);
+            }
+            if y < 2 {
+                moves.push(blank + 3);
+            }
+            moves
+        }
+
+        fn apply(&mut self, mv: &usize) {
+            let blank = self.blank();
+            self.0.swap(blank, *mv);
+        }
+
+        fn is_goal(&self) -> bool {
+            self.0 == [1, 2, 3, 4, 5, 6, 7, 8, 0]
+        }
+
+        fn heuristic(&self) -> u64 {
+            (0..9)
+                .filter(|&i| self.0[i] != 0)
+                .map(|i| {
+                    let goal = self.0[i] as usize - 1;
+                    ((i % 3).abs_diff(goal % 3) + (i / 3).abs_diff(goal / 3)) as u64
+                })
+                .sum()
+        }
+    }
+
+    /// Walk a number line to `target`, where long strides are cheaper per unit.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Line(u64);
+
+    impl Puzzle for Line {
+        type Move = u64;
+
+        fn moves(&self) -> Vec<u64> {
+            vec![1, 4]
+                .into_iter()
+                .filter(|s| self.0 + s <= 10)
+                .collect()
+        }
+
+        fn apply(&mut self, stride: &u64) {
+            self.0 += stride;
+        }
+
+        fn is_goal(&self) -> bool {
+            self.0 == 10
+        }
+
+        fn cost(&self, stride: &u64) -> u64 {
+            if *stride == 1 { 3 } else { 5 }
+        }
+    }
+
+    #[test]
+    fn test_solve_tiles() {
+        let start = Tiles([8, 6, 7, 2, 5, 4, 3, 0, 1]);
+        let moves = solve_bfs(&start).unwrap();
+        assert_eq!(moves.len(), 31);
+
+        let (cost, astar_moves) = solve_astar(&start).unwrap();
+        assert_eq!(cost, 31);
+        let end = astar_moves.iter().fold(start.clone(), |s, mv| s.after(mv));
+        assert!(end.is_goal());
+
+        let easy = Tiles([1, 2, 3, 4, 0, 6, 7, 5, 8]);
+        assert_eq!(solve_ida_star(&easy), Some((2, vec![7, 8])));
+
+        let unsolvable = Tiles([2, 1, 3, 4, 5, 6, 7, 8, 0]);
+        assert_eq!(solve_bfs(&unsolvable), None);
+    }
+
+    #[test]
+    fn test_solve_costs() {
+        assert_eq!(solve_bfs(&Line(0)).unwrap(), vec![1, 1, 4, 4]);
+        assert_eq!(solve_astar(&Line(0)), Some((16, vec![1, 1, 4, 4])));
+        assert_eq!(solve_ida_star(&Line(0)).map(|(cost, _)| cost), Some(16));
+        assert_eq!(solve_ida_star(&Line(11)), None);
+    }
+}
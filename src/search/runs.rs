@@ -0,0 +1,138 @@
+use super::dijkstra;
+use crate::grid::{Direction, Grid, neighbor_in_direction};
+use crate::math::two_dimensional::Point;
+
+/// How far a walker must and may move in a straight line before turning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunLimits {
+    /// Steps required in a direction before turning or stopping at the goal
+    pub min: u8,
+    /// Steps allowed in a direction before a turn is forced
+    pub max: u8,
+}
+
+impl RunLimits {
+    pub fn new(min: u8, max: u8) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Cheapest orthogonal walk across `grid` from `start` to `goal` that never reverses and respects `limits` on
+/// straight-line runs. `cost` gives the cost of entering a cell, or `None` if it can't be entered.
+///
+/// The search state is `(position, direction, run length)`. Returns the total cost and every cell visited, including
+/// both endpoints.
+pub fn dijkstra_with_runs<T, F>(
+    grid: &Grid<T>,
+    start: Point,
+    goal: Point,
+    limits: RunLimits,
+    mut cost: F,
+) -> Option<(u64, Vec<Point>)>
+where
+    F: FnMut(Point, &T) -> Option<u64>,
+{
+    type State = (Point, Option<Direction>, u8);
+
+    let successors = |&(position, direction, run): &State| {
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .filter(|&d| match direction {
+            None => true,
+            Some(current) if d == current => run < limits.max,
+            Some(current) => d != current.opposite() && run >= limits.min,
+        })
+        .filter_map(|d| {
+            let next = neighbor_in_direction(grid, d, position)?;
+            let step = cost(next.position, &grid[next.position])?;
+            let run = if Some(d) == direction { run + 1 } else { 1 };
+            Some(((next.position, Some(d), run), step))
+        })
+        .collect::<Vec<_>>()
+    };
+    let is_goal = |&(position, direction, run): &State| {
+        position == goal && (direction.is_none() || run >= limits.min)
+    };
+
+    let (total, path) = dijkstra((start, None, 0), successors, is_goal)?;
+    Some((total, path.into_iter().map(|(p, _, _)| p).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(rows: &[&str]) -> Grid<u64> {
+        Grid::try_from(
+            rows.iter()
+                .map(|row| row.bytes().map(|b| (b - b'0') as u64).collect())
+                .collect::<Vec<Vec<_>>>(),
+        )
+        .unwrap()
+    }
+
+    fn corner_to_corner(grid: &Grid<u64>, limits: RunLimits) -> Option<u64> {
+        let goal = Point::new(grid.width() - 1, grid.height() - 1);
+        dijkstra_with_runs(grid, Point::new(0, 0), goal, limits, |_, &c| Some(c))
+            .map(|(cost, _)| cost)
+    }
+
+    #[test]
+    fn test_dijkstra_with_runs() {
+        let grid = digits(&[
+            "2413432311323",
+            "3215453535623",
+            "3255245654254",
+            "3446585845452",
+            "4546657867536",
+            "1438598798454",
+            "4457876987766",
+            "3637877979653",
+            "4654967986887",
+            "4564679986453",
+            "1224686865563",
+            "2546548887735",
+            "4322674655533",
+        ]);
+        assert_eq!(corner_to_corner(&grid, RunLimits::new(1, 3)), Some(102));
+        assert_eq!(corner_to_corner(&grid, RunLimits::new(4, 10)), Some(94));
+
+        let grid = digits(&[
+            "111111111111",
+            "999999999991",
+            "999999999991",
+            "999999999991",
+            "999999999991",
+        ]);
+        assert_eq!(corner_to_corner(&grid, RunLimits::new(4, 10)), Some(71));
+    }
+
+    #[test]
+    fn test_dijkstra_with_runs_path() {
+        let grid = digits(&["1111", "9191", "1111"]);
+        let (cost, path) = dijkstra_with_runs(
+            &grid,
+            Point::new(0, 0),
+            Point::new(0, 2),
+            RunLimits::new(1, 3),
+            |_, &c| (c != 9).then_some(c),
+        )
+        .unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(
+            path,
+            [(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)].map(|(x, y)| Point::new(x, y))
+        );
+
+        let start = Point::new(1, 1);
+        assert_eq!(
+            dijkstra_with_runs(&grid, start, start, RunLimits::new(4, 10), |_, &c| Some(c)),
+            Some((0, vec![start]))
+        );
+    }
+}
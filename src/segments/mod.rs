@@ -0,0 +1,268 @@
+use crate::error::{Error, Result};
+use std::fmt::{self, Display, Formatter};
+
+/// A run of `len` consecutive blocks, belonging to file `id` or free when `id` is `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub id: Option<usize>,
+    pub len: usize,
+}
+
+impl Segment {
+    pub fn file(id: usize, len: usize) -> Self {
+        Self { id: Some(id), len }
+    }
+
+    pub fn free(len: usize) -> Self {
+        Self { id: None, len }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
+/// A disk-like sequence of file and free segments, laid out end to end from position 0, with the operations used to
+/// compact it: splitting segments, moving files into free space, and a positional checksum.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Segments {
+    segments: Vec<Segment>,
+}
+
+impl Segments {
+    /// Segments in order, dropping empty ones.
+    pub fn new(segments: impl IntoIterator<Item = Segment>) -> Self {
+        Self {
+            segments: segments.into_iter().filter(|s| s.len > 0).collect(),
+        }
+    }
+
+    /// Parses a dense disk map such as `"12345"`: digits alternate between file and free lengths, and files are
+    /// numbered from 0 in order.
+    pub fn from_dense(map: &str) -> Result<Self> {
+        let map = map.trim();
+        let segments = map
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let len = c.to_digit(10).ok_or_else(|| Error::Parse {
+                    message: format!("Invalid length {c:?} in disk map"),
+                    line: None,
+                    column: Some(i + 1),
+                    text: Some(map.to_string()),
+                })? as usize;
+                Ok(if i % 2 == 0 {
+                    Segment::file(i / 2, len)
+                } else {
+                    Segment::free(len)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(segments))
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Each segment with its starting position.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, &Segment)> {
+        self.segments.iter().scan(0, |start, segment| {
+            let position = *start;
+            *start += segment.len;
+            Some((position, segment))
+        })
+    }
+
+    /// The total number of blocks, free or not.
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|s| s.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Splits the segment at `index` after its first `len` blocks, so it becomes two segments with the same id.
+    pub fn split(&mut self, index: usize, len: usize) {
+        let segment = &mut self.segments[index];
+        assert!(
+            len <= segment.len,
+            "can't split {len} blocks off {segment:?}"
+        );
+        if len == 0 || len == segment.len {
+            return;
+        }
+        let rest = Segment {
+            id: segment.id,
+            len: segment.len - len,
+        };
+        segment.len = len;
+        self.segments.insert(index + 1, rest);
+    }
+
+    /// Moves the file at `file` to the start of the free segment at `free`, leaving free space behind and merging
+    /// neighboring free segments.
+    ///
+    /// # Panics
+    ///
+    /// If `file` isn't a file, `free` isn't free, or the file doesn't fit.
+    pub fn place(&mut self, file: usize, free: usize) {
+        let moving = self.segments[file];
+        let space = self.segments[free];
+        assert!(
+            !moving.is_free() && space.is_free(),
+            "can only move a file into free space"
+        );
+        assert!(
+            moving.len <= space.len,
+            "{moving:?} doesn't fit in {space:?}"
+        );
+
+        self.segments[file].id = None;
+        self.split(free, moving.len);
+        self.segments[free].id = moving.id;
+        self.merge_free();
+    }
+
+    /// Moves single blocks from the end of the disk into the leftmost free blocks until no gaps remain, splitting
+    /// files as needed.
+    pub fn compact_blocks(&mut self) {
+        let mut blocks = self
+            .segments
+            .iter()
+            .flat_map(|s| std::iter::repeat_n(s.id, s.len))
+            .collect::<Vec<_>>();
+        let (mut left, mut right) = (0, blocks.len());
+        loop {
+            while left < right && blocks[left].is_some() {
+                left += 1;
+            }
+            while right > left && blocks[right - 1].is_none() {
+                right -= 1;
+            }
+            if right - left < 2 {
+                break;
+            }
+            blocks.swap(left, right - 1);
+        }
+
+        let mut segments: Vec<Segment> = Vec::new();
+        for id in blocks {
+            match segments.last_mut() {
+                Some(last) if last.id == id => last.len += 1,
+                _ => segments.push(Segment { id, len: 1 }),
+            }
+        }
+        self.segments = segments;
+    }
+
+    /// Moves each whole file once, from the highest id down, into the leftmost free segment before it that can hold
+    /// it. Files with no such space stay put.
+    pub fn compact_files(&mut self) {
+        let Some(max_id) = self.segments.iter().filter_map(|s| s.id).max() else {
+            return;
+        };
+        for id in (0..=max_id).rev() {
+            let Some(file) = self.segments.iter().position(|s| s.id == Some(id)) else {
+                continue;
+            };
+            let len = self.segments[file].len;
+            if let Some(free) = self.segments[..file]
+                .iter()
+                .position(|s| s.is_free() && s.len >= len)
+            {
+                self.place(file, free);
+            }
+        }
+    }
+
+    /// The sum over all file blocks of position times file id.
+    pub fn checksum(&self) -> u64 {
+        self.positions()
+            .filter_map(|(start, s)| s.id.map(|id| (start, s.len, id)))
+            .flat_map(|(start, len, id)| (start..start + len).map(move |p| (p * id) as u64))
+            .sum()
+    }
+
+    fn merge_free(&mut self) {
+        self.segments.dedup_by(|next, previous| {
+            let merge = previous.is_free() && next.is_free();
+            if merge {
+                previous.len += next.len;
+            }
+            merge
+        });
+        self.segments.retain(|s| s.len > 0);
+    }
+}
+
+/// Draws the blocks as in the puzzle text: file ids as digits (wrapping past 9) and free blocks as `.`.
+impl Display for Segments {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            let c = match segment.id {
+                Some(id) => char::from_digit((id % 10) as u32, 10).unwrap_or('?'),
+                None => '.',
+            };
+            for _ in 0..segment.len {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    const EXAMPLE: &str = "2333133121414131402";
+
+    #[test]
+    fn test_from_dense() -> Result<()> {
+        let disk = Segments::from_dense("12345")?;
+        assert_eq!(disk.to_string(), "0..111....22222");
+        assert_eq!(disk.len(), 15);
+        assert!(Segments::from_dense("12x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_blocks() -> Result<()> {
+        let mut disk = Segments::from_dense(EXAMPLE)?;
+        disk.compact_blocks();
+        assert_eq!(
+            disk.to_string(),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(disk.checksum(), 1928);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_files() -> Result<()> {
+        let mut disk = Segments::from_dense(EXAMPLE)?;
+        disk.compact_files();
+        assert_eq!(
+            disk.to_string(),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+        assert_eq!(disk.checksum(), 2858);
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_and_place() {
+        let mut disk = Segments::new([Segment::file(0, 3), Segment::free(2), Segment::file(1, 3)]);
+        disk.split(2, 1);
+        assert_eq!(disk.segments().len(), 4);
+        disk.place(3, 1);
+        assert_eq!(disk.to_string(), "000111..");
+        assert_eq!(
+            disk.positions().map(|(p, _)| p).collect::<Vec<_>>(),
+            [0, 3, 5, 6]
+        );
+    }
+}
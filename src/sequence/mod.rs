@@ -0,0 +1,161 @@
+mod sweep;
+
+pub use sweep::{Boundary, max_overlap, sweep};
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// Sums of every window of `size` consecutive values, computed with a running total.
+pub fn windows_sum<T>(values: &[T], size: usize) -> impl Iterator<Item = T> + '_
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    assert!(size > 0, "window size must be positive");
+    let mut sum = values
+        .iter()
+        .take(size - 1)
+        .fold(T::default(), |acc, &v| acc + v);
+
+    values
+        .iter()
+        .enumerate()
+        .skip(size - 1)
+        .map(move |(i, &v)| {
+            sum = sum + v;
+            let window = sum;
+            sum = sum - values[i + 1 - size];
+            window
+        })
+}
+
+/// How many windows of `size` consecutive values have a larger sum than the window before. Adjacent windows share all
+/// but one value, so only the value entering and the value leaving need comparing.
+pub fn count_increases<T: PartialOrd>(values: &[T], size: usize) -> usize {
+    assert!(size > 0, "window size must be positive");
+    values
+        .iter()
+        .zip(values.iter().skip(size))
+        .filter(|(leaving, entering)| entering > leaving)
+        .count()
+}
+
+/// The extreme value of every window of `size` consecutive values, where `compare` returning `Less` means the first
+/// argument is more extreme. Uses a monotonic deque, so the whole pass is O(n).
+pub fn sliding_extreme_by<T, I, F>(
+    values: I,
+    size: usize,
+    mut compare: F,
+) -> impl Iterator<Item = T>
+where
+    T: Clone,
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(size > 0, "window size must be positive");
+    // candidates for the extreme, most extreme first, along with their positions
+    let mut deque: VecDeque<(usize, T)> = VecDeque::new();
+
+    values
+        .into_iter()
+        .enumerate()
+        .filter_map(move |(i, value)| {
+            while deque
+                .back()
+                .is_some_and(|(_, back)| compare(&value, back) != Ordering::Greater)
+            {
+                deque.pop_back();
+            }
+            deque.push_back((i, value));
+            if deque.front().is_some_and(|&(j, _)| j + size <= i) {
+                deque.pop_front();
+            }
+
+            if i + 1 < size {
+                return None;
+            }
+            deque.front().map(|(_, v)| v.clone())
+        })
+}
+
+/// The minimum of every window of `size` consecutive values.
+pub fn sliding_min<T, I>(values: I, size: usize) -> impl Iterator<Item = T>
+where
+    T: Clone + Ord,
+    I: IntoIterator<Item = T>,
+{
+    sliding_extreme_by(values, size, T::cmp)
+}
+
+/// The maximum of every window of `size` consecutive values.
+pub fn sliding_max<T, I>(values: I, size: usize) -> impl Iterator<Item = T>
+where
+    T: Clone + Ord,
+    I: IntoIterator<Item = T>,
+{
+    sliding_extreme_by(values, size, |a, b| b.cmp(a))
+}
+
+/// The index of the first value not less than `x` in sorted `values`: how many values are below `x`.
+pub fn lower_bound<T: Ord>(values: &[T], x: &T) -> usize {
+    values.partition_point(|v| v < x)
+}
+
+/// The index of the first value greater than `x` in sorted `values`: how many values are at most `x`.
+pub fn upper_bound<T: Ord>(values: &[T], x: &T) -> usize {
+    values.partition_point(|v| v <= x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let values = [1, 3, 3, 3, 7, 9];
+        assert_eq!(lower_bound(&values, &3), 1);
+        assert_eq!(upper_bound(&values, &3), 4);
+        assert_eq!(lower_bound(&values, &4), 4);
+        assert_eq!(upper_bound(&values, &4), 4);
+        assert_eq!(lower_bound(&values, &0), 0);
+        assert_eq!(upper_bound(&values, &9), 6);
+        assert_eq!(lower_bound::<i32>(&[], &5), 0);
+    }
+
+    const DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_windows_sum() {
+        let sums: Vec<u32> = windows_sum(&DEPTHS, 3).collect();
+        assert_eq!(sums, [607, 618, 618, 617, 647, 716, 769, 792]);
+        assert_eq!(windows_sum(&DEPTHS, 1).count(), 10);
+        assert_eq!(windows_sum(&DEPTHS, 11).count(), 0);
+    }
+
+    #[test]
+    fn test_count_increases() {
+        assert_eq!(count_increases(&DEPTHS, 1), 7);
+        assert_eq!(count_increases(&DEPTHS, 3), 5);
+        assert_eq!(count_increases(&DEPTHS, 10), 0);
+    }
+
+    #[test]
+    fn test_sliding_extremes() {
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(
+            sliding_max(values, 3).collect::<Vec<_>>(),
+            [3, 3, 5, 5, 6, 7]
+        );
+        assert_eq!(
+            sliding_min(values, 3).collect::<Vec<_>>(),
+            [-1, -3, -3, -3, 3, 3]
+        );
+        assert_eq!(sliding_min(values, 1).collect::<Vec<_>>(), values);
+        assert_eq!(sliding_max([2, 2, 2, 1], 2).collect::<Vec<_>>(), [2, 2, 2]);
+        assert_eq!(sliding_max(values, 9).count(), 0);
+
+        let words = ["pear", "fig", "banana", "kiwi"];
+        let longest: Vec<_> = sliding_extreme_by(words, 2, |a, b| b.len().cmp(&a.len())).collect();
+        assert_eq!(longest, ["pear", "banana", "banana"]);
+    }
+}
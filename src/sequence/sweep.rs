@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+/// The intervals covering a stretch of the line, from `position` up to the next boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boundary<'a, T, P> {
+    pub position: T,
+    /// Payloads of the intervals covering `position`, in input order
+    pub active: Vec<&'a P>,
+}
+
+impl<T, P> Boundary<'_, T, P> {
+    pub fn count(&self) -> usize {
+        self.active.len()
+    }
+}
+
+/// Sweeps the half-open intervals `start..end`, returning a [`Boundary`] at every position where an interval starts
+/// or ends, with the intervals active from there to the next boundary. Empty intervals are ignored.
+pub fn sweep<T: Ord + Copy, P>(intervals: &[(T, T, P)]) -> Vec<Boundary<'_, T, P>> {
+    let mut events = intervals
+        .iter()
+        .enumerate()
+        .filter(|(_, (start, end, _))| start < end)
+        .flat_map(|(i, &(start, end, _))| [(start, true, i), (end, false, i)])
+        .collect::<Vec<_>>();
+    events.sort_by_key(|&(position, _, i)| (position, i));
+
+    let mut active = BTreeSet::new();
+    let mut boundaries = Vec::new();
+    let mut events = events.into_iter().peekable();
+    while let Some(&(position, _, _)) = events.peek() {
+        while let Some((_, starts, i)) = events.next_if(|&(p, _, _)| p == position) {
+            if starts {
+                active.insert(i);
+            } else {
+                active.remove(&i);
+            }
+        }
+        boundaries.push(Boundary {
+            position,
+            active: active.iter().map(|&i| &intervals[i].2).collect(),
+        });
+    }
+    boundaries
+}
+
+/// The first position covered by the most intervals, with how many cover it, or `None` if no interval is
+/// non-empty.
+pub fn max_overlap<T: Ord + Copy, P>(intervals: &[(T, T, P)]) -> Option<(T, usize)> {
+    sweep(intervals)
+        .iter()
+        .map(|b| (b.position, b.count()))
+        .filter(|&(_, count)| count > 0)
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep() {
+        let intervals = [(0, 10, 'a'), (5, 15, 'b'), (10, 12, 'c'), (20, 20, 'd')];
+        let boundaries = sweep(&intervals)
+            .into_iter()
+            .map(|b| (b.position, b.active.into_iter().collect::<String>()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            boundaries,
+            [
+                (0, "a".to_string()),
+                (5, "ab".to_string()),
+                (10, "bc".to_string()),
+                (12, "b".to_string()),
+                (15, String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_overlap() {
+        // 2018 day 4: guard 10 is asleep during minutes 5..25, 30..55 and 24..29
+        let naps = [(5, 25, 10), (30, 55, 10), (24, 29, 10)];
+        assert_eq!(max_overlap(&naps), Some((24, 2)));
+        assert_eq!(max_overlap::<i32, ()>(&[]), None);
+        assert_eq!(max_overlap(&[(3, 3, ())]), None);
+    }
+}
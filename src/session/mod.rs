@@ -0,0 +1,55 @@
+use crate::config::Config;
+
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "adventofcode.com";
+#[cfg(feature = "keyring")]
+const KEYRING_USER: &str = "session";
+
+/// Returns the configured session cookie, falling back to the OS keyring when the `keyring` feature is enabled.
+pub fn resolve(config: &Config) -> Option<String> {
+    if config.session.is_some() {
+        return config.session.clone();
+    }
+
+    #[cfg(feature = "keyring")]
+    {
+        load()
+            .inspect_err(|e| log::warn!("Failed to read session cookie from keyring: {e}"))
+            .ok()
+            .flatten()
+    }
+
+    #[cfg(not(feature = "keyring"))]
+    {
+        None
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn entry() -> anyhow::Result<keyring::Entry> {
+    Ok(keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?)
+}
+
+#[cfg(feature = "keyring")]
+pub fn load() -> anyhow::Result<Option<String>> {
+    match entry()?.get_password() {
+        Ok(session) => Ok(Some(session)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(feature = "keyring")]
+pub fn store(session: &str) -> anyhow::Result<()> {
+    Ok(entry()?.set_password(session.trim())?)
+}
+
+/// Removes any stored session cookie, returning whether one was present.
+#[cfg(feature = "keyring")]
+pub fn clear() -> anyhow::Result<bool> {
+    match entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
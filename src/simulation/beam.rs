@@ -0,0 +1,143 @@
+use crate::grid::{Cardinal, Grid, neighbor_in_direction};
+use crate::math::two_dimensional::Point;
+use std::collections::HashSet;
+
+/// Every (cell, incoming heading) pair some beam passed through, as found by [`trace_beams`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeamTrace {
+    states: HashSet<(Point, Cardinal)>,
+}
+
+impl BeamTrace {
+    /// Each cell a beam entered, paired with the heading it was travelling when it did.
+    pub fn states(&self) -> &HashSet<(Point, Cardinal)> {
+        &self.states
+    }
+
+    /// The cells at least one beam passed through.
+    pub fn energized(&self) -> HashSet<Point> {
+        self.states.iter().map(|&(position, _)| position).collect()
+    }
+
+    pub fn energized_count(&self) -> usize {
+        self.energized().len()
+    }
+}
+
+/// Follows a beam entering `start` while travelling `heading`, and every beam split from it. On entering a cell, a
+/// beam continues in each heading `rule(heading, &cell)` returns (none absorbs it, several split it) and leaves the
+/// grid at its edge. A beam entering a cell with a heading some beam already had there is dropped, so loops end.
+pub fn trace_beams<T, R, I>(
+    grid: &Grid<T>,
+    start: Point,
+    heading: Cardinal,
+    mut rule: R,
+) -> BeamTrace
+where
+    R: FnMut(Cardinal, &T) -> I,
+    I: IntoIterator<Item = Cardinal>,
+{
+    let mut states = HashSet::new();
+    let mut beams = Vec::new();
+    if grid.get(start).is_some() {
+        beams.push((start, heading));
+    }
+
+    while let Some((position, heading)) = beams.pop() {
+        if !states.insert((position, heading)) {
+            continue;
+        }
+        for next in rule(heading, &grid[position]) {
+            if let Some(neighbor) = neighbor_in_direction(grid, next.into(), position) {
+                beams.push((neighbor.position, next));
+            }
+        }
+    }
+    BeamTrace { states }
+}
+
+/// Every way into the grid from outside it: each edge cell with the heading pointing inward, so corner cells appear
+/// twice.
+pub fn edge_entries<T>(grid: &Grid<T>) -> Vec<(Point, Cardinal)> {
+    let (width, height) = (grid.width(), grid.height());
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    (0..width)
+        .flat_map(|x| {
+            [
+                (Point::new(x, 0), Cardinal::Down),
+                (Point::new(x, height - 1), Cardinal::Up),
+            ]
+        })
+        .chain((0..height).flat_map(|y| {
+            [
+                (Point::new(0, y), Cardinal::Right),
+                (Point::new(width - 1, y), Cardinal::Left),
+            ]
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::YAxis;
+    use anyhow::Result;
+
+    const CONTRAPTION: [&str; 10] = [
+        r".|...\....",
+        r"|.-.\.....",
+        r".....|-...",
+        r"........|.",
+        r"..........",
+        r".........\",
+        r"..../.\\..",
+        r".-.-/..|..",
+        r".|....-|.\",
+        r"..//.|....",
+    ];
+
+    fn mirrors(heading: Cardinal, cell: &char) -> Vec<Cardinal> {
+        use Cardinal::*;
+        match (cell, heading) {
+            ('/', Right) | ('\\', Left) => vec![Up],
+            ('/', Left) | ('\\', Right) => vec![Down],
+            ('/', Up) | ('\\', Down) => vec![Right],
+            ('/', Down) | ('\\', Up) => vec![Left],
+            ('|', Left | Right) => vec![Up, Down],
+            ('-', Up | Down) => vec![Left, Right],
+            _ => vec![heading],
+        }
+    }
+
+    #[test]
+    fn test_trace_beams() -> Result<()> {
+        let grid = Grid::from_lines(&CONTRAPTION, YAxis::Down, |c| c)?;
+        let trace = trace_beams(&grid, Point::new(0, 0), Cardinal::Right, mirrors);
+        assert_eq!(trace.energized_count(), 46);
+        assert!(
+            trace
+                .states()
+                .contains(&(Point::new(1, 0), Cardinal::Right))
+        );
+
+        let best = edge_entries(&grid)
+            .into_iter()
+            .map(|(start, heading)| trace_beams(&grid, start, heading, mirrors).energized_count())
+            .max();
+        assert_eq!(best, Some(51));
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_beams_absorbed_and_outside() -> Result<()> {
+        let grid = Grid::from_lines(&CONTRAPTION, YAxis::Down, |c| c)?;
+        let trace = trace_beams(&grid, Point::new(0, 0), Cardinal::Right, |_, _| None);
+        assert_eq!(trace.energized_count(), 1);
+        let trace = trace_beams(&grid, Point::new(10, 0), Cardinal::Left, mirrors);
+        assert_eq!(trace.energized_count(), 0);
+        assert_eq!(edge_entries(&grid).len(), 40);
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+mod beam;
+mod zobrist;
+
+pub use beam::{BeamTrace, edge_entries, trace_beams};
+use std::collections::HashMap;
+use std::hash::Hash;
+pub use zobrist::ZobristKeys;
+
+/// A deterministic simulation that can be advanced one step at a time.
+pub trait Simulation {
+    type Key: Eq + Hash;
+
+    fn step(&mut self);
+
+    /// Identifies the current state. Two steps with equal keys must evolve identically from then on.
+    fn state_key(&self) -> Self::Key;
+
+    /// A running total that isn't captured by the key, such as the height of a tower of rocks. It is assumed to grow
+    /// by the same amount on every repetition of a cycle.
+    fn progress(&self) -> i64 {
+        0
+    }
+
+    /// Called when [`run_steps`] skips `steps` steps over which `progress` would have grown by `gained`.
+    fn fast_forward(&mut self, _steps: u64, _gained: i64) {}
+}
+
+/// A cycle found by [`run_steps`]: the state after `start` steps recurs every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: u64,
+    pub period: u64,
+}
+
+/// Advances `sim` by `n` steps. Once a state key repeats, whole cycles are skipped via
+/// [`Simulation::fast_forward`] and the remainder is stepped normally. Returns the cycle, if one was found.
+pub fn run_steps<S: Simulation>(sim: &mut S, n: u64) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut step = 0;
+
+    while step < n {
+        let progress = sim.progress();
+        match seen.insert(sim.state_key(), (step, progress)) {
+            Some((start, earlier)) => {
+                let period = step - start;
+                let cycles = (n - step) / period;
+                sim.fast_forward(cycles * period, cycles as i64 * (progress - earlier));
+
+                for _ in 0..(n - step) % period {
+                    sim.step();
+                }
+                return Some(Cycle { start, period });
+            }
+            None => {
+                sim.step();
+                step += 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Generator {
+        value: u64,
+        total: i64,
+    }
+
+    impl Simulation for Generator {
+        type Key = u64;
+
+        fn step(&mut self) {
+            self.value = (self.value * self.value + 1) % 1009;
+            self.total += self.value as i64;
+        }
+
+        fn state_key(&self) -> u64 {
+            self.value
+        }
+
+        fn progress(&self) -> i64 {
+            self.total
+        }
+
+        fn fast_forward(&mut self, _steps: u64, gained: i64) {
+            self.total += gained;
+        }
+    }
+
+    fn generator() -> Generator {
+        Generator { value: 7, total: 0 }
+    }
+
+    #[test]
+    fn test_run_steps() {
+        for n in [0, 1, 5, 100, 2500] {
+            let mut expected = generator();
+            (0..n).for_each(|_| expected.step());
+
+            let mut sim = generator();
+            run_steps(&mut sim, n);
+            assert_eq!(
+                (sim.value, sim.total),
+                (expected.value, expected.total),
+                "after {n} steps"
+            );
+        }
+
+        let mut sim = generator();
+        let cycle = run_steps(&mut sim, 1_000_000_000_000).unwrap();
+        let mut check = generator();
+        (0..cycle.start).for_each(|_| check.step());
+        let key = check.value;
+        (0..cycle.period).for_each(|_| check.step());
+        assert_eq!(check.value, key);
+    }
+
+    #[test]
+    fn test_run_steps_without_cycle() {
+        struct Counter(u64);
+
+        impl Simulation for Counter {
+            type Key = u64;
+
+            fn step(&mut self) {
+                self.0 += 1;
+            }
+
+            fn state_key(&self) -> u64 {
+                self.0
+            }
+        }
+
+        let mut counter = Counter(0);
+        assert_eq!(run_steps(&mut counter, 1000), None);
+        assert_eq!(counter.0, 1000);
+    }
+}
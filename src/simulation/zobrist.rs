@@ -0,0 +1,119 @@
+use crate::grid::Grid;
+use crate::math::Rng;
+use crate::math::two_dimensional::Point;
+
+const DEFAULT_SEED: u64 = 0x5eed_a0c0_2015_1225;
+
+/// Random keys for Zobrist hashing: one per (cell, value) pair, so a state's hash is the XOR of the keys of its
+/// cells' values. Changing one cell updates the hash with two XORs instead of rehashing the whole state, which makes
+/// the hash a cheap [`Simulation::state_key`](super::Simulation::state_key). Values are small indices, e.g. 0 for
+/// empty, 1 for a round rock and 2 for a cube rock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZobristKeys {
+    values: usize,
+    width: usize,
+    keys: Vec<u64>,
+}
+
+impl ZobristKeys {
+    pub fn new(cells: usize, values: usize) -> Self {
+        Self::with_seed(cells, values, DEFAULT_SEED)
+    }
+
+    /// Keys drawn from `seed`; the same seed always gives the same keys.
+    pub fn with_seed(cells: usize, values: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let keys = (0..cells * values).map(|_| rng.next_u64()).collect();
+        Self {
+            values,
+            width: cells,
+            keys,
+        }
+    }
+
+    /// Keys for every cell of `grid`, addressed by [`Point`] with [`at`](Self::at).
+    pub fn for_grid<T>(grid: &Grid<T>, values: usize) -> Self {
+        Self {
+            width: grid.width(),
+            ..Self::new(grid.width() * grid.height(), values)
+        }
+    }
+
+    pub fn key(&self, cell: usize, value: usize) -> u64 {
+        debug_assert!(value < self.values, "value {value} out of range");
+        self.keys[cell * self.values + value]
+    }
+
+    /// The key for `value` at a grid position, for keys made with [`for_grid`](Self::for_grid).
+    pub fn at(&self, position: Point, value: usize) -> u64 {
+        self.key(position.y * self.width + position.x, value)
+    }
+
+    /// The hash of a whole state, given each cell's value in order.
+    pub fn hash(&self, values: impl IntoIterator<Item = usize>) -> u64 {
+        values
+            .into_iter()
+            .enumerate()
+            .fold(0, |hash, (cell, value)| hash ^ self.key(cell, value))
+    }
+
+    /// The hash of a grid, mapping each cell to its value index with `value`.
+    pub fn hash_grid<T>(&self, grid: &Grid<T>, value: impl Fn(&T) -> usize) -> u64 {
+        self.hash(grid.iter().flatten().map(value))
+    }
+
+    /// Updates `hash` for a cell changing from `old` to `new`.
+    pub fn update(&self, hash: &mut u64, cell: usize, old: usize, new: usize) {
+        *hash ^= self.key(cell, old) ^ self.key(cell, new);
+    }
+
+    /// Updates `hash` for a grid cell changing from `old` to `new`.
+    pub fn update_at(&self, hash: &mut u64, position: Point, old: usize, new: usize) {
+        *hash ^= self.at(position, old) ^ self.at(position, new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    fn value(c: &char) -> usize {
+        ".O#".find(*c).unwrap()
+    }
+
+    #[test]
+    fn test_incremental_matches_full_hash() -> Result<()> {
+        let mut grid = Grid::try_from(vec![
+            "O.#".chars().collect::<Vec<_>>(),
+            "..O".chars().collect(),
+        ])?;
+        let keys = ZobristKeys::for_grid(&grid, 3);
+        let mut hash = keys.hash_grid(&grid, value);
+
+        // roll the rock at (0, 0) down, then the one at (2, 1) left
+        for (from, to) in [
+            (Point::new(0, 0), Point::new(0, 1)),
+            (Point::new(2, 1), Point::new(1, 1)),
+        ] {
+            keys.update_at(&mut hash, from, value(&grid[from]), value(&'.'));
+            keys.update_at(&mut hash, to, value(&grid[to]), value(&'O'));
+            grid[from] = '.';
+            grid[to] = 'O';
+            assert_eq!(hash, keys.hash_grid(&grid, value));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_keys() {
+        let keys = ZobristKeys::new(4, 2);
+        assert_eq!(keys, ZobristKeys::new(4, 2));
+        assert_ne!(keys, ZobristKeys::with_seed(4, 2, 1));
+        assert_ne!(keys.hash([0, 1, 0, 0]), keys.hash([1, 0, 0, 0]));
+
+        let mut hash = keys.hash([0, 0, 0, 0]);
+        keys.update(&mut hash, 2, 0, 1);
+        assert_eq!(hash, keys.hash([0, 0, 1, 0]));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::answer::Answer;
+use anyhow::Result;
+use std::fmt::Display;
+
+pub trait Solver {
+    fn part1(&self, lines: &[String]) -> Result<Answer>;
+
+    fn part2(&self, lines: &[String]) -> Result<Answer>;
+
+    fn solve(&self, part: u8, lines: &[String]) -> Result<Answer> {
+        match part {
+            1 => self.part1(lines),
+            2 => self.part2(lines),
+            _ => anyhow::bail!("Invalid part: {part}"),
+        }
+    }
+}
+
+/// A [`Solver`] whose parts return their natural types (`u64`, `i128`, `String`), so callers such as tests can compare
+/// typed values. Every `Solution` is also a `Solver`, converting answers with `Into<Answer>`.
+pub trait Solution {
+    type Output1: Display + Eq + Into<Answer>;
+    type Output2: Display + Eq + Into<Answer>;
+
+    fn part1(&self, lines: &[String]) -> Result<Self::Output1>;
+
+    fn part2(&self, lines: &[String]) -> Result<Self::Output2>;
+}
+
+impl<S: Solution> Solver for S {
+    fn part1(&self, lines: &[String]) -> Result<Answer> {
+        Solution::part1(self, lines).map(Into::into)
+    }
+
+    fn part2(&self, lines: &[String]) -> Result<Answer> {
+        Solution::part2(self, lines).map(Into::into)
+    }
+}
+
+/// Runs a [`Solver`] on the example input and asserts each listed part's answer, so a day's regression test is one
+/// call. Lines come from `init_test()` unless given with `lines = ...`; expected values are compared with
+/// [`Answer::matches`].
+///
+/// ```ignore
+/// #[test]
+/// fn example() {
+///     assert_example!(Day01, part1 = 142, part2 = "abc");
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_example {
+    ($solver:expr, lines = $lines:expr $(, $part:ident = $expected:expr)+ $(,)?) => {{
+        let solver = &$solver;
+        let lines: &[String] = &$lines;
+        $(
+            let part = $crate::assert_example!(@number $part);
+            let answer = $crate::Solver::solve(solver, part, lines)
+                .unwrap_or_else(|e| panic!("part {part} failed on the example: {e:#}"));
+            let expected = $expected.to_string();
+            assert!(
+                answer.matches(&expected),
+                "part {part} of the example: expected {expected}, got {answer}"
+            );
+        )+
+    }};
+    (@number part1) => { 1 };
+    (@number part2) => { 2 };
+    ($solver:expr $(, $part:ident = $expected:expr)+ $(,)?) => {{
+        let lines = $crate::init_test().expect("failed to read the example input");
+        $crate::assert_example!($solver, lines = lines $(, $part = $expected)+)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Output1 = usize;
+        type Output2 = String;
+
+        fn part1(&self, lines: &[String]) -> Result<usize> {
+            Ok(lines.iter().map(String::len).sum())
+        }
+
+        fn part2(&self, lines: &[String]) -> Result<String> {
+            Ok(lines.concat())
+        }
+    }
+
+    #[test]
+    fn test_solution_is_solver() -> Result<()> {
+        let lines = ["ab".to_string(), "cde".to_string()];
+        assert_eq!(Solution::part1(&Lengths, &lines)?, 5);
+        assert_eq!(Lengths.solve(1, &lines)?, Answer::UInt(5));
+        assert_eq!(Lengths.solve(2, &lines)?, Answer::Str("abcde".into()));
+        assert!(Lengths.solve(3, &lines).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_example() {
+        let lines = vec!["ab".to_string(), "cde".to_string()];
+        assert_example!(Lengths, lines = lines, part1 = 5, part2 = "abcde");
+        assert_example!(Lengths, lines = lines, part2 = "abcde");
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 of the example: expected 6, got 5")]
+    fn test_assert_example_mismatch() {
+        let lines = vec!["ab".to_string(), "cde".to_string()];
+        assert_example!(Lengths, lines = lines, part1 = 6);
+    }
+}
@@ -0,0 +1,213 @@
+/// Levenshtein distance between `a` and `b`: the fewest single-character insertions, deletions and substitutions
+/// that turn one into the other.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // only the previous row of the table is needed
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// One step of an [`edit_script`], turning `a` into `b` from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// The character is the same in both
+    Keep(char),
+    /// `a`'s character is replaced by `b`'s
+    Substitute(char, char),
+    /// `a`'s character is removed
+    Delete(char),
+    /// `b`'s character is added
+    Insert(char),
+}
+
+/// A shortest sequence of edits turning `a` into `b`, with one [`Edit::Keep`] for each unchanged character, so it has
+/// [`edit_distance`] steps that aren't keeps. When there are several, keeps and substitutions are preferred over
+/// deletions, and deletions over insertions.
+pub fn edit_script(a: &str, b: &str) -> Vec<Edit> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // `table[i][j]` is the edit distance between `a[i..]` and `b[j..]`, so the script can be read off front to back
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..=a.len()).rev() {
+        for j in (0..=b.len()).rev() {
+            table[i][j] = if i == a.len() {
+                b.len() - j
+            } else if j == b.len() {
+                a.len() - i
+            } else {
+                (table[i + 1][j + 1] + usize::from(a[i] != b[j]))
+                    .min(table[i + 1][j] + 1)
+                    .min(table[i][j + 1] + 1)
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut script = Vec::new();
+    while i < a.len() || j < b.len() {
+        let edit = if i < a.len()
+            && j < b.len()
+            && table[i][j] == table[i + 1][j + 1] + usize::from(a[i] != b[j])
+        {
+            i += 1;
+            j += 1;
+            if a[i - 1] == b[j - 1] {
+                Edit::Keep(a[i - 1])
+            } else {
+                Edit::Substitute(a[i - 1], b[j - 1])
+            }
+        } else if i < a.len() && table[i][j] == table[i + 1][j] + 1 {
+            i += 1;
+            Edit::Delete(a[i - 1])
+        } else {
+            j += 1;
+            Edit::Insert(b[j - 1])
+        };
+        script.push(edit);
+    }
+    script
+}
+
+/// `table[i][j]` is the length of the longest common subsequence of `a[i..]` and `b[j..]`.
+fn lcs_table(a: &[char], b: &[char]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    table
+}
+
+/// Length of the longest common subsequence of `a` and `b`.
+pub fn lcs_length(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    lcs_table(&a, &b)[0][0]
+}
+
+/// A longest common subsequence of `a` and `b`. When there are several, the one that is earliest in `a` wins.
+pub fn lcs(a: &str, b: &str) -> String {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let table = lcs_table(&a, &b);
+
+    let (mut i, mut j) = (0, 0);
+    let mut result = String::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+/// The columns of `lines` as strings, read top to bottom, so column-oriented text (the crate stacks diagram, vertical
+/// words) can be parsed row by row. Lines shorter than the longest are padded with spaces.
+pub fn transpose_lines<S: AsRef<str>>(lines: &[S]) -> Vec<String> {
+    let rows: Vec<Vec<char>> = lines.iter().map(|l| l.as_ref().chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (0..width)
+        .map(|x| {
+            rows.iter()
+                .map(|row| row.get(x).copied().unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transpose_lines() {
+        let stacks = ["    [D]", "[N] [C]", "[Z] [M] [P]", " 1   2   3"];
+        let columns = transpose_lines(&stacks);
+        assert_eq!(columns.len(), 11);
+        assert_eq!(columns[1], " NZ1");
+        assert_eq!(columns[5], "DCM2");
+        assert_eq!(columns[9], "  P3");
+        assert_eq!(transpose_lines(&columns)[0], "    [D]    ");
+
+        assert!(transpose_lines::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("fghij", "fguij"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+        assert_eq!(edit_distance("é", "e"), 1);
+    }
+
+    #[test]
+    fn test_edit_script() {
+        use Edit::*;
+        assert_eq!(
+            edit_script("kitten", "sitting"),
+            [
+                Substitute('k', 's'),
+                Keep('i'),
+                Keep('t'),
+                Keep('t'),
+                Substitute('e', 'i'),
+                Keep('n'),
+                Insert('g'),
+            ]
+        );
+        assert_eq!(
+            edit_script("fghij", "fguij"),
+            [
+                Keep('f'),
+                Keep('g'),
+                Substitute('h', 'u'),
+                Keep('i'),
+                Keep('j')
+            ]
+        );
+        assert_eq!(edit_script("ab", ""), [Delete('a'), Delete('b')]);
+        assert_eq!(edit_script("", "ab"), [Insert('a'), Insert('b')]);
+        assert_eq!(
+            edit_script("abc", "ac"),
+            [Keep('a'), Delete('b'), Keep('c')]
+        );
+
+        let script = edit_script("intention", "execution");
+        let changes = script.iter().filter(|e| !matches!(e, Keep(_))).count();
+        assert_eq!(changes, edit_distance("intention", "execution"));
+    }
+
+    #[test]
+    fn test_lcs() {
+        assert_eq!(lcs_length("ABCBDAB", "BDCABA"), 4);
+        assert_eq!(lcs("ABCBDAB", "BDCABA").len(), 4);
+        assert_eq!(lcs("fghij", "fguij"), "fgij");
+        assert_eq!(lcs("abc", "xyz"), "");
+        assert_eq!(lcs_length("", "abc"), 0);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::grid::Grid;
+use crate::math::two_dimensional::Point;
+use std::fmt::{Display, Write as _};
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(50);
+
+/// A terminal color for [`Viz`] frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn ansi(self) -> String {
+        match self {
+            Color::Black => "30".into(),
+            Color::Red => "31".into(),
+            Color::Green => "32".into(),
+            Color::Yellow => "33".into(),
+            Color::Blue => "34".into(),
+            Color::Magenta => "35".into(),
+            Color::Cyan => "36".into(),
+            Color::White => "37".into(),
+            Color::Gray => "90".into(),
+            Color::Rgb(r, g, b) => format!("38;2;{r};{g};{b}"),
+        }
+    }
+
+    fn css(self) -> String {
+        let (r, g, b) = match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 49, 49),
+            Color::Green => (13, 188, 121),
+            Color::Yellow => (229, 229, 16),
+            Color::Blue => (36, 114, 200),
+            Color::Magenta => (188, 63, 188),
+            Color::Cyan => (17, 168, 205),
+            Color::White => (229, 229, 229),
+            Color::Gray => (128, 128, 128),
+            Color::Rgb(r, g, b) => (r, g, b),
+        };
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// Animates grid simulations in the terminal, redrawing the whole grid each step. Does nothing unless enabled, which
+/// [`RunContext`](crate::RunContext) does for `--viz`, so frames can be left in solutions.
+#[derive(Debug, Clone, Copy)]
+pub struct Viz {
+    enabled: bool,
+    delay: Duration,
+}
+
+impl Default for Viz {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl Viz {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            delay: DEFAULT_DELAY,
+        }
+    }
+
+    /// Sets the pause after each frame.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Clears the terminal and draws `grid`, mapping each cell to what to draw and an optional color, then pauses for
+    /// the frame delay.
+    pub fn frame<T, F, O>(&self, grid: &Grid<T>, mapper: F) -> std::io::Result<()>
+    where
+        F: Fn(&T) -> (O, Option<Color>),
+        O: Display,
+    {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        // move home and clear the screen
+        write!(stdout, "\x1b[H\x1b[2J{}", render(grid, mapper))?;
+        stdout.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+/// Renders `grid` with ANSI colors, only switching colors where they change along a row.
+fn render<T, F, O>(grid: &Grid<T>, mapper: F) -> String
+where
+    F: Fn(&T) -> (O, Option<Color>),
+    O: Display,
+{
+    let mut out = String::new();
+    for row in grid {
+        let mut current = None;
+        for cell in row {
+            let (shown, color) = mapper(cell);
+            if color != current {
+                match color {
+                    Some(c) => write!(out, "\x1b[{}m", c.ansi()),
+                    None => write!(out, "\x1b[0m"),
+                }
+                .expect("writing to a String can't fail");
+                current = color;
+            }
+            write!(out, "{shown}").expect("writing to a String can't fail");
+        }
+        if current.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Side length of a cell in [`render_svg`] output, in pixels.
+const SVG_CELL: usize = 10;
+
+/// Renders `grid` as an SVG image, filling each cell with the color `style` picks for it (or leaving it blank), and
+/// overlaying `path` as a line through the centers of its cells.
+pub fn render_svg<T, F>(grid: &Grid<T>, style: F, path: &[Point]) -> String
+where
+    F: Fn(&T) -> Option<Color>,
+{
+    let (width, height) = (grid.width() * SVG_CELL, grid.height() * SVG_CELL);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+    );
+
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if let Some(color) = style(cell) {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{}\"/>",
+                    x * SVG_CELL,
+                    y * SVG_CELL,
+                    color.css()
+                )
+                .expect("writing to a String can't fail");
+            }
+        }
+    }
+
+    if !path.is_empty() {
+        let points: Vec<String> = path
+            .iter()
+            .map(|p| {
+                let center = |v: usize| v * SVG_CELL + SVG_CELL / 2;
+                format!("{},{}", center(p.x), center(p.y))
+            })
+            .collect();
+        writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\" \
+             stroke-linejoin=\"round\" shape-rendering=\"auto\"/>",
+            points.join(" "),
+            Color::Red.css()
+        )
+        .expect("writing to a String can't fail");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::try_from(vec![vec!['.', '#', '#'], vec!['o', '.', '.']]).unwrap();
+        let rendered = render(&grid, |&c| {
+            let color = match c {
+                '#' => Some(Color::Gray),
+                'o' => Some(Color::Rgb(255, 200, 0)),
+                _ => None,
+            };
+            (c, color)
+        });
+        assert_eq!(
+            rendered,
+            ".\x1b[90m##\x1b[0m\n\x1b[38;2;255;200;0mo\x1b[0m..\n"
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let grid = Grid::try_from(vec![vec!['.', '#'], vec!['.', '.']]).unwrap();
+        let path = [Point::new(0, 0), Point::new(0, 1), Point::new(1, 1)];
+        let svg = render_svg(&grid, |&c| (c == '#').then_some(Color::Gray), &path);
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"20\"")
+        );
+        assert_eq!(svg.matches("<rect").count(), 1);
+        assert!(svg.contains(r##"<rect x="10" y="0" width="10" height="10" fill="#808080"/>"##));
+        assert!(svg.contains(r#"points="5,5 5,15 15,15""#));
+        assert!(svg.ends_with("</svg>\n"));
+
+        assert!(!render_svg(&grid, |_| None, &[]).contains("polyline"));
+    }
+}